@@ -1,133 +1,130 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::parse::{Parse, ParseStream};
 use syn::spanned::Spanned;
-use syn::{parse_macro_input, Attribute, Data, DeriveInput, ItemFn, LitChar};
-
-struct TileChar(LitChar);
-
-impl Parse for TileChar {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        Ok(Self(LitChar::parse(input)?))
-    }
-}
-
-fn tile_tag(attrs: &[Attribute]) -> syn::Result<Option<LitChar>> {
-    for attr in attrs {
-        if attr.path().is_ident("tile") {
-            return Ok(Some(attr.parse_args::<TileChar>()?.0));
-        }
-    }
-    Ok(None)
-}
-
+use syn::{parse_macro_input, DeriveInput, Ident, Item, Type};
+
+mod parse;
+mod tile;
+
+/// Derives `ParseTile` from `#[tile(...)]` attributes on each variant.
+///
+/// An attribute lists the characters that parse as that variant, either
+/// singly (`#[tile('.', ' ')]`) or as inclusive ranges
+/// (`#[tile('0'..='9')]`). A variant with a single unnamed field captures
+/// the matched character: `char` fields store it as is, numeric fields
+/// store its digit value. At most one variant may be marked
+/// `#[tile(other)]` to accept any character not claimed elsewhere, and
+/// variants marked `skip` are never parsed.
+///
+/// Newtype structs take the attribute on the struct itself.
 #[proc_macro_derive(ParseTile, attributes(tile))]
 pub fn derive_parsetile(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
-    let ident = input.ident.clone();
-
-    let enum_data = match input.data {
-        Data::Enum(d) => d,
-        _ => {
-            return syn::Error::new(
-                input.span(),
-                "ParseTile derive macro can only be used on enum types",
-            )
-            .into_compile_error()
-            .into()
-        }
-    };
-
-    let mut parse_cases = TokenStream::new();
-
-    for variant in enum_data.variants {
-        let variant_ident = variant.ident;
-        match tile_tag(&variant.attrs) {
-            Ok(Some(ch)) => parse_cases.extend(std::iter::once(TokenStream::from(quote! {
-                #ch => Some(Self::#variant_ident),
-            }))),
-            Ok(None) => {}
-            Err(e) => return e.into_compile_error().into(),
-        }
-    }
-
-    let expanded = quote! {
-        impl ::aoc::map::ParseTile for #ident {
-            fn from_char(ch: char) -> Option<Self> {
-                match ch {
-                    #parse_cases
-                    _ => None
-                }
-            }
-        }
-    };
-
-    TokenStream::from(expanded).into()
+    tile::derive_parsetile(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
+/// Derives `DisplayTile` using the same `#[tile(...)]` attributes as
+/// `ParseTile`. Every variant needs one; unit variants display as their
+/// first listed character, so display-only tiles are written as
+/// `#[tile('O', skip)]`.
+///
+/// Tiles can also be given a style for coloured rendering with
+/// `fg = "..."`, `bg = "..."` and `bold`, such as
+/// `#[tile('#', fg = "grey")]`. Colours are the basic terminal colour
+/// names or `"#rrggbb"`.
 #[proc_macro_derive(DisplayTile, attributes(tile))]
 pub fn derive_displaytile(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
-    let ident = input.ident.clone();
-
-    let enum_data = match input.data {
-        Data::Enum(d) => d,
-        _ => {
-            return syn::Error::new(
-                input.span(),
-                "DisplayTile derive macro can only be used on enum types",
-            )
-            .into_compile_error()
-            .into()
-        }
-    };
+    tile::derive_displaytile(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-    let mut parse_cases = TokenStream::new();
+/// Derives `AocParse` for a struct from a `#[parse("...")]` template.
+///
+/// See the `aoc::parse::AocParse` documentation for the template syntax.
+#[proc_macro_derive(AocParse, attributes(parse))]
+pub fn derive_aocparse(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    parse::derive_aocparse(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-    for variant in enum_data.variants {
-        let variant_ident = variant.ident;
-        match tile_tag(&variant.attrs) {
-            Ok(Some(ch)) => parse_cases.extend(std::iter::once(TokenStream::from(quote! {
-                Self::#variant_ident => #ch,
-            }))),
-            Ok(None) => {}
-            Err(e) => return e.into_compile_error().into(),
-        }
+/// Reads the day number from a solver named like `day5` or `Day5`.
+fn day_number(ident: &Ident) -> syn::Result<u32> {
+    let name = ident.to_string();
+    let day = (name
+        .strip_prefix("day")
+        .or_else(|| name.strip_prefix("Day")))
+    .and_then(|n| n.parse::<u32>().ok());
+    match day {
+        Some(day @ 1..=25) => Ok(day),
+        _ => Err(syn::Error::new(
+            ident.span(),
+            "solvers must be named after their day, such as `day5` or `Day5`",
+        )),
     }
+}
 
-    let expanded = quote! {
-        impl ::aoc::map::DisplayTile for #ident {
-            fn to_char(self) -> char {
-                match self {
-                    #parse_cases
-                }
-            }
+fn register(item: Item) -> syn::Result<TokenStream> {
+    let (day, solve) = match &item {
+        Item::Fn(f) => {
+            let fn_name = &f.sig.ident;
+            (
+                day_number(fn_name)?,
+                quote!(|input| ::aoc::runner::Report::report(#fn_name(input))),
+            )
+        }
+        Item::Impl(imp) => {
+            let ty = &imp.self_ty;
+            let ident = match (&imp.trait_, &**ty) {
+                (Some(_), Type::Path(p)) if p.qself.is_none() => p.path.get_ident(),
+                _ => None,
+            };
+            let Some(ident) = ident else {
+                return Err(syn::Error::new(
+                    imp.span(),
+                    "expected `impl Solver for DayN`",
+                ));
+            };
+            (day_number(ident)?, quote!(::aoc::solver::solve::<#ty>))
+        }
+        item => {
+            return Err(syn::Error::new(
+                item.span(),
+                "#[aoc::main] expects a solver function or a `Solver` impl",
+            ))
         }
     };
 
-    TokenStream::from(expanded).into()
+    Ok(quote! {
+        #item
+
+        pub static DAY: ::aoc::runner::Day = ::aoc::runner::Day {
+            day: #day,
+            dir: ::std::env!("CARGO_MANIFEST_DIR"),
+            solve: #solve,
+        };
+    })
 }
 
+/// Registers the annotated solver for a day, generating a
+/// `pub static DAY: aoc::runner::Day` for the day's binary and the
+/// multi-day runner to call.
+///
+/// The solver is either a function named after its day, as in `fn day5`,
+/// that takes the input as `&str` and returns `()`, a `Solution`, or a
+/// `Result` of either; or an `impl Solver for Day5` block.
 #[proc_macro_attribute]
 pub fn main(
     _attr: proc_macro::TokenStream,
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let input = parse_macro_input!(item as ItemFn);
-    let fn_name = &input.sig.ident.clone();
-
-    quote! {
-        #input
-
-        fn main() -> impl ::std::process::Termination {
-            use ::std::io::Read;
-            static mut inp: ::std::string::String = ::std::string::String::new();
-            let s = unsafe {
-                ::std::io::stdin().read_to_string(&mut inp).unwrap();
-                inp.as_ref()
-            };
-            #fn_name(s)
-        }
-    }
-    .into()
+    let item = parse_macro_input!(item as Item);
+    register(item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
@@ -0,0 +1,300 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Field, Fields, Ident, LitStr, Type};
+
+use std::collections::BTreeMap;
+
+/// A piece of a `#[parse("...")]` template.
+enum Piece {
+    Literal(String),
+    Newline,
+    Field {
+        name: String,
+        index: Option<usize>,
+        sep: Option<String>,
+    },
+}
+
+fn parse_placeholder(spec: &str, lit: &LitStr) -> syn::Result<Piece> {
+    let (path, sep) = match spec.split_once(':') {
+        Some((path, sep)) if !sep.is_empty() => (path, Some(sep.to_string())),
+        Some(_) => {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!("empty separator in `{{{spec}}}`"),
+            ))
+        }
+        None => (spec, None),
+    };
+    let (name, index) = match path.split_once('.') {
+        Some((name, index)) => match index.parse() {
+            Ok(index) => (name, Some(index)),
+            Err(_) => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    format!("expected a tuple index after `{name}.` in `{{{spec}}}`"),
+                ))
+            }
+        },
+        None => (path, None),
+    };
+    if name.is_empty() {
+        return Err(syn::Error::new(
+            lit.span(),
+            "empty `{}` placeholder, expected a field name",
+        ));
+    }
+    Ok(Piece::Field {
+        name: name.to_string(),
+        index,
+        sep,
+    })
+}
+
+fn parse_template(lit: &LitStr) -> syn::Result<Vec<Piece>> {
+    let template = lit.value();
+    let mut pieces = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+
+    let flush = |literal: &mut String, pieces: &mut Vec<Piece>| {
+        if !literal.is_empty() {
+            pieces.push(Piece::Literal(std::mem::take(literal)));
+        }
+    };
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut spec = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(ch) => spec.push(ch),
+                        None => {
+                            return Err(syn::Error::new(
+                                lit.span(),
+                                "unclosed `{` in parse template, use `{{` for a literal brace",
+                            ))
+                        }
+                    }
+                }
+                flush(&mut literal, &mut pieces);
+                pieces.push(parse_placeholder(&spec, lit)?);
+            }
+            '}' => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "unmatched `}` in parse template, use `}}` for a literal brace",
+                ))
+            }
+            '\n' => {
+                flush(&mut literal, &mut pieces);
+                pieces.push(Piece::Newline);
+            }
+            ch => literal.push(ch),
+        }
+    }
+    flush(&mut literal, &mut pieces);
+    Ok(pieces)
+}
+
+fn template(input: &DeriveInput) -> syn::Result<LitStr> {
+    for attr in &input.attrs {
+        if attr.path().is_ident("parse") {
+            return attr.parse_args();
+        }
+    }
+    Err(syn::Error::new(
+        input.ident.span(),
+        "AocParse derive needs a `#[parse(\"...\")]` template",
+    ))
+}
+
+/// Whether `field` is marked `#[parse(default)]`, to leave it out of the
+/// template.
+fn is_default(field: &Field) -> syn::Result<bool> {
+    let mut default = false;
+    for attr in &field.attrs {
+        if attr.path().is_ident("parse") {
+            let arg: Ident = attr.parse_args()?;
+            if arg != "default" {
+                return Err(syn::Error::new(arg.span(), "expected `#[parse(default)]`"));
+            }
+            default = true;
+        }
+    }
+    Ok(default)
+}
+
+/// How a struct field is filled in from the template.
+enum Usage {
+    Whole,
+    Elements(BTreeMap<usize, Ident>),
+}
+
+pub fn derive_aocparse(input: DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(s) => match &s.fields {
+            Fields::Named(f) => &f.named,
+            _ => {
+                return Err(syn::Error::new(
+                    s.fields.span(),
+                    "AocParse derive needs a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "AocParse derive can only be used on struct types",
+            ))
+        }
+    };
+    let lit = template(&input)?;
+
+    let mut steps = TokenStream::new();
+    let mut usage: BTreeMap<String, Usage> = BTreeMap::new();
+    for piece in parse_template(&lit)? {
+        match piece {
+            Piece::Literal(text) => steps.extend(quote! {
+                let (i, _) = ::aoc::parse::__derive::tag::<E>(#text, i)?;
+            }),
+            Piece::Newline => steps.extend(quote! {
+                let (i, _) = ::aoc::parse::__derive::line_ending::<E>(i)?;
+            }),
+            Piece::Field { name, index, sep } => {
+                if !fields
+                    .iter()
+                    .any(|f| f.ident.as_ref().is_some_and(|f| *f == name))
+                {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        format!("`{ident}` has no field named `{name}`"),
+                    ));
+                }
+
+                let (var, label) = match index {
+                    Some(idx) => (
+                        format_ident!("__aoc_{}_{}", name, idx),
+                        format!("{name}.{idx}"),
+                    ),
+                    None => (format_ident!("__aoc_{}", name), name.clone()),
+                };
+                let duplicate = match (usage.get_mut(&name), index) {
+                    (None, None) => {
+                        usage.insert(name.clone(), Usage::Whole);
+                        false
+                    }
+                    (None, Some(idx)) => {
+                        usage.insert(name.clone(), Usage::Elements([(idx, var.clone())].into()));
+                        false
+                    }
+                    (Some(Usage::Elements(elems)), Some(idx)) => {
+                        elems.insert(idx, var.clone()).is_some()
+                    }
+                    (Some(_), _) => true,
+                };
+                if duplicate {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        format!("`{{{label}}}` conflicts with another placeholder for `{name}`"),
+                    ));
+                }
+
+                steps.extend(match sep {
+                    Some(sep) => quote! {
+                        let (i, #var) = ::aoc::parse::__derive::list::<_, E>(#label, #sep, i)?;
+                    },
+                    None => quote! {
+                        let (i, #var) = ::aoc::parse::__derive::field::<_, E>(#label, i)?;
+                    },
+                });
+            }
+        }
+    }
+
+    let mut inits = TokenStream::new();
+    for field in fields {
+        let name = field.ident.as_ref().unwrap();
+        let usage = usage.remove(&name.to_string());
+        if is_default(field)? {
+            if usage.is_some() {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!(
+                        "`{name}` is in the parse template, so it can't be `#[parse(default)]`"
+                    ),
+                ));
+            }
+            inits.extend(quote!(#name: ::core::default::Default::default(),));
+            continue;
+        }
+        inits.extend(match usage {
+            Some(Usage::Whole) => {
+                let var = format_ident!("__aoc_{}", name);
+                quote!(#name: #var,)
+            }
+            Some(Usage::Elements(elems)) => {
+                // The last element used stands in for the length of a
+                // field whose type isn't spelled as a tuple.
+                let last = *elems.keys().next_back().unwrap();
+                let arity = match &field.ty {
+                    Type::Tuple(tuple) => tuple.elems.len(),
+                    _ => last + 1,
+                };
+                if last >= arity {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        format!(
+                            "`{{{name}.{last}}}` is out of range, `{name}` has {arity} elements"
+                        ),
+                    ));
+                }
+                if let Some(missing) = (0..arity).find(|idx| !elems.contains_key(idx)) {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        format!("missing placeholder `{{{name}.{missing}}}`"),
+                    ));
+                }
+                let vars = elems.values();
+                quote!(#name: (#(#vars,)*),)
+            }
+            None => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!(
+                        "`{name}` is not in the parse template, \
+                         add a `{{{name}}}` placeholder or mark it `#[parse(default)]`"
+                    ),
+                ))
+            }
+        });
+    }
+
+    let name = LitStr::new(&ident.to_string(), Span::call_site());
+    Ok(quote! {
+        impl ::aoc::parse::AocParse for #ident {
+            fn parse<'a, E>(i: &'a str) -> ::aoc::nom::IResult<&'a str, Self, E>
+            where
+                E: ::aoc::nom::error::ParseError<&'a str>
+                    + ::aoc::nom::error::ContextError<&'a str>,
+            {
+                ::aoc::parse::__derive::context(#name, i, |i| {
+                    #steps
+                    Ok((i, Self { #inits }))
+                })
+            }
+        }
+    })
+}
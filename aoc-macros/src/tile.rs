@@ -0,0 +1,542 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Fields, Ident, Lit, LitChar, LitStr, Token, Type};
+
+/// Collects errors so that every problem with a derive is reported at
+/// once, rather than one per compile.
+#[derive(Default)]
+struct Errors(Option<syn::Error>);
+
+impl Errors {
+    fn push(&mut self, err: syn::Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(err),
+            None => self.0 = Some(err),
+        }
+    }
+
+    /// Gives `value` if nothing went wrong, or else all of the errors.
+    fn finish<T>(self, value: T) -> syn::Result<T> {
+        self.0.map_or(Ok(value), Err)
+    }
+}
+
+/// A single entry of a `#[tile(...)]` attribute.
+enum TileItem {
+    Char(LitChar),
+    Range(LitChar, LitChar),
+    Other(Ident),
+    Skip(Ident),
+    Fg(Ident, TokenStream),
+    Bg(Ident, TokenStream),
+    Bold(Ident),
+}
+
+/// Parses a colour name or `"#rrggbb"` into an `aoc::style::Color`.
+fn parse_color(input: ParseStream) -> syn::Result<TokenStream> {
+    let lit: LitStr = input.parse()?;
+    let value = lit.value();
+    let name = match value.as_str() {
+        "black" => quote!(Black),
+        "red" => quote!(Red),
+        "green" => quote!(Green),
+        "yellow" => quote!(Yellow),
+        "blue" => quote!(Blue),
+        "magenta" => quote!(Magenta),
+        "cyan" => quote!(Cyan),
+        "white" => quote!(White),
+        "grey" | "gray" => quote!(Grey),
+        hex => {
+            let rgb = (hex.strip_prefix('#'))
+                .filter(|h| h.len() == 6)
+                .and_then(|h| u32::from_str_radix(h, 16).ok());
+            let Some(rgb) = rgb else {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    format!(
+                        "unknown colour {value:?}, expected a name such as \"red\" or \"grey\", or \"#rrggbb\""
+                    ),
+                ));
+            };
+            let [_, r, g, b] = rgb.to_be_bytes();
+            quote!(Rgb(::aoc::style::Rgb(#r, #g, #b)))
+        }
+    };
+    Ok(quote!(::aoc::style::Color::#name))
+}
+
+/// Parses a character literal, pointing at the offending token with a
+/// more helpful message than syn's default if it is some other literal.
+fn parse_char(input: ParseStream) -> syn::Result<LitChar> {
+    let ch = match input.parse::<Lit>()? {
+        Lit::Char(ch) => ch,
+        Lit::Str(s) if s.value().chars().count() == 1 => {
+            return Err(syn::Error::new(
+                s.span(),
+                format!(
+                    "expected a character literal, found a string; use {:?} instead",
+                    s.value().chars().next().unwrap()
+                ),
+            ))
+        }
+        lit => {
+            return Err(syn::Error::new(
+                lit.span(),
+                "expected a character literal such as '#'",
+            ))
+        }
+    };
+    if ch.value() == '\n' {
+        return Err(syn::Error::new(
+            ch.span(),
+            "'\\n' cannot be a tile character, it separates map rows",
+        ));
+    }
+    Ok(ch)
+}
+
+impl Parse for TileItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Ident) {
+            let ident: Ident = input.parse()?;
+            return if ident == "other" {
+                Ok(Self::Other(ident))
+            } else if ident == "skip" {
+                Ok(Self::Skip(ident))
+            } else if ident == "bold" {
+                Ok(Self::Bold(ident))
+            } else if ident == "fg" || ident == "bg" {
+                input.parse::<Token![=]>()?;
+                let color = parse_color(input)?;
+                Ok(if ident == "fg" {
+                    Self::Fg(ident, color)
+                } else {
+                    Self::Bg(ident, color)
+                })
+            } else {
+                Err(syn::Error::new(
+                    ident.span(),
+                    format!(
+                        "unknown tile option `{ident}`, expected `other`, `skip`, `fg`, `bg` or `bold`"
+                    ),
+                ))
+            };
+        }
+
+        let start = parse_char(input)?;
+        if input.peek(Token![..=]) {
+            input.parse::<Token![..=]>()?;
+            let end = parse_char(input)?;
+            if end.value() < start.value() {
+                return Err(syn::Error::new(end.span(), "tile range is empty"));
+            }
+            Ok(Self::Range(start, end))
+        } else {
+            Ok(Self::Char(start))
+        }
+    }
+}
+
+impl TileItem {
+    fn pattern(&self) -> Option<TokenStream> {
+        match self {
+            Self::Char(ch) => Some(quote!(#ch)),
+            Self::Range(start, end) => Some(quote!(#start..=#end)),
+            _ => None,
+        }
+    }
+
+    fn first_char(&self) -> Option<&LitChar> {
+        match self {
+            Self::Char(ch) | Self::Range(ch, _) => Some(ch),
+            _ => None,
+        }
+    }
+
+    fn bounds(&self) -> Option<(char, char)> {
+        match self {
+            Self::Char(ch) => Some((ch.value(), ch.value())),
+            Self::Range(start, end) => Some((start.value(), end.value())),
+            _ => None,
+        }
+    }
+
+    fn span(&self) -> Span {
+        match self {
+            Self::Char(ch) => ch.span(),
+            Self::Range(start, end) => start.span().join(end.span()).unwrap_or(start.span()),
+            Self::Other(ident) | Self::Skip(ident) | Self::Bold(ident) => ident.span(),
+            Self::Fg(ident, _) | Self::Bg(ident, _) => ident.span(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct TileSpec {
+    items: Vec<TileItem>,
+    other: Option<Ident>,
+    skip: Option<Ident>,
+    fg: Option<TokenStream>,
+    bg: Option<TokenStream>,
+    bold: bool,
+}
+
+impl TileSpec {
+    /// The `aoc::style::Style` for the tile, if it has any style options.
+    fn style(&self) -> Option<TokenStream> {
+        if self.fg.is_none() && self.bg.is_none() && !self.bold {
+            return None;
+        }
+        let option = |color: &Option<TokenStream>| match color {
+            Some(color) => quote!(Some(#color)),
+            None => quote!(None),
+        };
+        let (fg, bg, bold) = (option(&self.fg), option(&self.bg), self.bold);
+        Some(quote! {
+            ::aoc::style::Style {
+                fg: #fg,
+                bg: #bg,
+                bold: #bold,
+            }
+        })
+    }
+}
+
+/// Parses the comma-separated items of a `#[tile(...)]` attribute. An item
+/// that doesn't parse is skipped up to the next comma, so that the rest
+/// are still checked.
+fn parse_items(input: ParseStream, errors: &mut Errors) -> syn::Result<Vec<TileItem>> {
+    let mut items = Vec::new();
+    while !input.is_empty() {
+        let parsed = match input.parse() {
+            Ok(item) => {
+                items.push(item);
+                true
+            }
+            Err(err) => {
+                errors.push(err);
+                false
+            }
+        };
+        if !input.is_empty() && !input.peek(Token![,]) {
+            // A bad item has already been reported, whatever follows it.
+            if parsed {
+                errors.push(input.error("expected `,`"));
+            }
+            while !input.is_empty() && !input.peek(Token![,]) {
+                input.parse::<TokenTree>()?;
+            }
+        }
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+    }
+    Ok(items)
+}
+
+fn tile_spec(attrs: &[Attribute], errors: &mut Errors) -> Option<TileSpec> {
+    let attr = attrs.iter().find(|attr| attr.path().is_ident("tile"))?;
+    let mut spec = TileSpec::default();
+    let items = match attr.parse_args_with(|input: ParseStream| parse_items(input, errors)) {
+        Ok(items) => items,
+        Err(err) => {
+            errors.push(err);
+            return Some(spec);
+        }
+    };
+    for item in items {
+        match item {
+            TileItem::Other(ident) => spec.other = Some(ident),
+            TileItem::Skip(ident) => spec.skip = Some(ident),
+            TileItem::Bold(_) => spec.bold = true,
+            TileItem::Fg(ident, color) | TileItem::Bg(ident, color) => {
+                let slot = if ident == "fg" {
+                    &mut spec.fg
+                } else {
+                    &mut spec.bg
+                };
+                if slot.is_some() {
+                    errors.push(syn::Error::new(
+                        ident.span(),
+                        format!("`{ident}` is given more than once"),
+                    ));
+                }
+                *slot = Some(color);
+            }
+            item => spec.items.push(item),
+        }
+    }
+    if let (Some(_), Some(skip)) = (&spec.other, &spec.skip) {
+        errors.push(syn::Error::new(
+            skip.span(),
+            "a tile cannot be both `other` and `skip`",
+        ));
+    }
+    Some(spec)
+}
+
+/// How the matched character is stored in a data-carrying tile.
+enum Capture {
+    /// The field is a `char` and receives the character itself.
+    Char,
+    /// The field is numeric and receives the value of the digit.
+    Digit(Box<Type>),
+}
+
+impl Capture {
+    fn from_fields(fields: &Fields) -> syn::Result<Option<Self>> {
+        match fields {
+            Fields::Unit => Ok(None),
+            Fields::Unnamed(f) if f.unnamed.len() == 1 => {
+                let ty = &f.unnamed[0].ty;
+                match ty {
+                    Type::Path(p) if p.qself.is_none() && p.path.is_ident("char") => {
+                        Ok(Some(Self::Char))
+                    }
+                    ty => Ok(Some(Self::Digit(Box::new(ty.clone())))),
+                }
+            }
+            f => Err(syn::Error::new(
+                f.span(),
+                "tiles can carry at most one unnamed field",
+            )),
+        }
+    }
+}
+
+/// One way of constructing the tile type: an enum variant, or the type
+/// itself for a newtype struct.
+struct Case {
+    name: Ident,
+    path: TokenStream,
+    capture: Option<Capture>,
+    spec: Option<TileSpec>,
+}
+
+impl Case {
+    fn construct(&self, ch: &Ident) -> TokenStream {
+        let path = &self.path;
+        match &self.capture {
+            None => quote!(#path),
+            Some(Capture::Char) => quote!(#path(#ch)),
+            Some(Capture::Digit(ty)) => quote!(#path((#ch as u32 - '0' as u32) as #ty)),
+        }
+    }
+
+    fn check_digits(&self, errors: &mut Errors) {
+        let (Some(Capture::Digit(_)), Some(spec)) = (&self.capture, &self.spec) else {
+            return;
+        };
+        if let Some(other) = &spec.other {
+            errors.push(syn::Error::new(
+                other.span(),
+                "`other` tiles can only capture into a `char` field",
+            ));
+        }
+        for item in &spec.items {
+            let Some((start, end)) = item.bounds() else {
+                continue;
+            };
+            if !start.is_ascii_digit() || !end.is_ascii_digit() {
+                errors.push(syn::Error::new(
+                    item.span(),
+                    "only the digits '0'..='9' can be captured into a numeric field",
+                ));
+            }
+        }
+    }
+}
+
+fn cases(input: &DeriveInput, derive: &str, errors: &mut Errors) -> syn::Result<Vec<Case>> {
+    let mut case = |name: &Ident, path, fields, attrs| {
+        let capture = Capture::from_fields(fields).unwrap_or_else(|err| {
+            errors.push(err);
+            None
+        });
+        let case = Case {
+            name: name.clone(),
+            path,
+            capture,
+            spec: tile_spec(attrs, errors),
+        };
+        case.check_digits(errors);
+        case
+    };
+
+    match &input.data {
+        Data::Enum(d) => Ok((d.variants.iter())
+            .map(|variant| {
+                let ident = &variant.ident;
+                case(ident, quote!(Self::#ident), &variant.fields, &variant.attrs)
+            })
+            .collect()),
+        Data::Struct(s) if matches!(s.fields, Fields::Unnamed(_)) => Ok(vec![case(
+            &input.ident,
+            quote!(Self),
+            &s.fields,
+            &input.attrs,
+        )]),
+        _ => Err(syn::Error::new(
+            input.span(),
+            format!("{derive} derive macro can only be used on enum or newtype struct types"),
+        )),
+    }
+}
+
+/// Rejects characters that more than one parsed tile would match, since
+/// only the first match arm could ever be reached.
+fn check_duplicates(cases: &[Case], errors: &mut Errors) {
+    let mut seen: Vec<(char, char, &Ident, &TileItem)> = Vec::new();
+    for case in cases {
+        let Some(spec) = &case.spec else { continue };
+        if spec.skip.is_some() {
+            continue;
+        }
+        for item in &spec.items {
+            let Some((start, end)) = item.bounds() else {
+                continue;
+            };
+            if let Some((prev_start, _, prev_name, prev_item)) =
+                seen.iter().find(|(s, e, _, _)| start <= *e && *s <= end)
+            {
+                let ch = start.max(*prev_start);
+                let mut err = syn::Error::new(
+                    item.span(),
+                    format!("tile character {ch:?} is already used by `{prev_name}`"),
+                );
+                err.combine(syn::Error::new(
+                    prev_item.span(),
+                    format!("{ch:?} first used by `{prev_name}` here"),
+                ));
+                errors.push(err);
+                continue;
+            }
+            seen.push((start, end, &case.name, item));
+        }
+    }
+}
+
+pub fn derive_parsetile(input: DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let ch = Ident::new("ch", Span::call_site());
+
+    let mut errors = Errors::default();
+    let cases = cases(&input, "ParseTile", &mut errors)?;
+    check_duplicates(&cases, &mut errors);
+
+    let mut parse_cases = TokenStream::new();
+    let mut other_case = None;
+    for case in &cases {
+        let Some(spec) = &case.spec else { continue };
+        if spec.skip.is_some() {
+            continue;
+        }
+        let patterns: Vec<_> = spec.items.iter().flat_map(TileItem::pattern).collect();
+        let construct = case.construct(&ch);
+        if !patterns.is_empty() {
+            let binding = case.capture.as_ref().map(|_| quote!(#ch @));
+            parse_cases.extend(quote! {
+                #binding (#(#patterns)|*) => Some(#construct),
+            });
+        }
+        if let Some(other) = &spec.other {
+            if other_case.is_some() {
+                errors.push(syn::Error::new(
+                    other.span(),
+                    "only one tile can be marked `other`",
+                ));
+            }
+            other_case = Some(construct);
+        }
+    }
+
+    let fallback = match other_case {
+        Some(construct) => quote! {
+            '\n' => None,
+            #ch => Some(#construct),
+        },
+        None => quote!(_ => None),
+    };
+
+    errors.finish(quote! {
+        impl ::aoc::map::ParseTile for #ident {
+            fn from_char(#ch: char) -> Option<Self> {
+                match #ch {
+                    #parse_cases
+                    #fallback
+                }
+            }
+        }
+    })
+}
+
+pub fn derive_displaytile(input: DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let val = Ident::new("val", Span::call_site());
+
+    let mut errors = Errors::default();
+    let mut display_cases = TokenStream::new();
+    let mut style_cases = TokenStream::new();
+    for case in cases(&input, "DisplayTile", &mut errors)? {
+        let Some(spec) = &case.spec else {
+            errors.push(syn::Error::new(
+                case.name.span(),
+                format!(
+                    "`{}` has no `#[tile(...)]` attribute, DisplayTile needs a character for every tile",
+                    case.name
+                ),
+            ));
+            continue;
+        };
+        let path = &case.path;
+        if let Some(style) = spec.style() {
+            style_cases.extend(quote!(#path { .. } => #style,));
+        }
+        display_cases.extend(match &case.capture {
+            Some(Capture::Char) => quote!(#path(#val) => #val,),
+            Some(Capture::Digit(_)) => quote! {
+                #path(#val) => char::from_digit(#val as u32, 10).expect("tile digit out of range"),
+            },
+            None => {
+                let Some(ch) = spec.items.iter().find_map(TileItem::first_char) else {
+                    let span = (spec.other.as_ref())
+                        .or(spec.skip.as_ref())
+                        .map_or(case.name.span(), Ident::span);
+                    errors.push(syn::Error::new(
+                        span,
+                        format!("`{}` needs a character to display as", case.name),
+                    ));
+                    continue;
+                };
+                quote!(#path => #ch,)
+            }
+        });
+    }
+
+    // Tiles without any style options keep the trait's plain default.
+    let to_style = (!style_cases.is_empty()).then(|| {
+        quote! {
+            fn to_style(self) -> ::aoc::style::Style {
+                #[allow(unreachable_patterns)]
+                match self {
+                    #style_cases
+                    _ => ::aoc::style::Style::PLAIN,
+                }
+            }
+        }
+    });
+
+    errors.finish(quote! {
+        impl ::aoc::map::DisplayTile for #ident {
+            fn to_char(self) -> char {
+                match self {
+                    #display_cases
+                }
+            }
+
+            #to_style
+        }
+    })
+}
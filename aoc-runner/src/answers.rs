@@ -0,0 +1,141 @@
+//! Known answers, loaded from an `answers.toml` such as:
+//!
+//! ```toml
+//! [day5]
+//! part1 = 143
+//! part2 = 123
+//!
+//! [day17]
+//! part1 = "5,7,3,0"
+//! ```
+
+use anyhow::{bail, Context, Result};
+use aoc::runner::Answers;
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+/// The expected answers for each day, either of which may be unknown.
+#[derive(Debug, Default)]
+pub struct Expected {
+    days: BTreeMap<u32, [Option<String>; 2]>,
+}
+
+fn value_string(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(s) => Some(s.clone()),
+        toml::Value::Integer(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+impl Expected {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let table: toml::Table =
+            (text.parse()).with_context(|| format!("failed to parse {}", path.display()))?;
+
+        let mut expected = Expected::default();
+        for (key, parts) in table {
+            let Some(day) = key.strip_prefix("day").and_then(|d| d.parse().ok()) else {
+                bail!(
+                    "unexpected key `{key}` in {}, expected `dayN`",
+                    path.display()
+                );
+            };
+            let Some(parts) = parts.as_table() else {
+                bail!("`{key}` in {} should be a table", path.display());
+            };
+            let mut answers = [None, None];
+            for (part, value) in parts {
+                let slot = match part.as_str() {
+                    "part1" => &mut answers[0],
+                    "part2" => &mut answers[1],
+                    _ => bail!("unexpected key `{key}.{part}` in {}", path.display()),
+                };
+                let value = value_string(value)
+                    .with_context(|| format!("`{key}.{part}` should be a string or integer"))?;
+                *slot = Some(value);
+            }
+            expected.days.insert(day, answers);
+        }
+        Ok(expected)
+    }
+
+    /// Whether any answer is known for `day`.
+    pub fn has(&self, day: u32) -> bool {
+        (self.days.get(&day)).is_some_and(|answers| answers.iter().any(Option::is_some))
+    }
+
+    /// Compares the answers for `day` with the expected ones.
+    pub fn check(&self, day: u32, answers: &Answers) -> Check {
+        let expected = self.days.get(&day).cloned().unwrap_or_default();
+        let mut check = Check::Pass;
+        let actual = [&answers.part1, &answers.part2];
+        for (part, (expected, actual)) in (1..).zip(expected.into_iter().zip(actual)) {
+            match expected {
+                None if check == Check::Pass => check = Check::Missing,
+                Some(expected) if expected != *actual => {
+                    let mismatch = Mismatch {
+                        day,
+                        part,
+                        expected,
+                        actual: actual.clone(),
+                    };
+                    match &mut check {
+                        Check::Fail(mismatches) => mismatches.push(mismatch),
+                        check => *check = Check::Fail(vec![mismatch]),
+                    }
+                }
+                _ => (),
+            }
+        }
+        check
+    }
+}
+
+/// An answer that differs from the expected one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub day: u32,
+    pub part: u32,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "day {} part {}: expected {}, got {}",
+            self.day, self.part, self.expected, self.actual
+        )
+    }
+}
+
+/// The outcome of checking a day's answers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    /// At least one part has no expected answer, and none are wrong.
+    Missing,
+    Fail(Vec<Mismatch>),
+    /// The solver returned an error instead of answers.
+    Error,
+    /// There are expected answers but no input to check them against.
+    NoInput,
+}
+
+impl Check {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Check::Pass => "pass",
+            Check::Missing => "missing",
+            Check::Fail(_) => "FAIL",
+            Check::Error => "error",
+            Check::NoInput => "missing",
+        }
+    }
+}
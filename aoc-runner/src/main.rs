@@ -0,0 +1,254 @@
+//! Runs any or all of the days against inputs stored as `inputs/dayNN.txt`,
+//! or in `$AOC_INPUT_DIR`, and prints a table of the answers and how long
+//! each day took.
+
+use anyhow::{bail, Context, Result};
+use aoc::inputs::Inputs;
+use aoc::runner::{format_duration, load_input, print_error, Answers, Day};
+
+use answers::{Check, Expected};
+
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+mod answers;
+
+static DAYS: &[&Day] = &[
+    &day1::DAY,
+    &day2::DAY,
+    &day3::DAY,
+    &day4::DAY,
+    &day5::DAY,
+    &day6::DAY,
+    &day7::DAY,
+    &day8::DAY,
+    &day9::DAY,
+    &day10::DAY,
+    &day11::DAY,
+    &day12::DAY,
+    &day13::DAY,
+    &day14::DAY,
+    &day15::DAY,
+    &day16::DAY,
+    &day17::DAY,
+    &day18::DAY,
+    &day19::DAY,
+    &day20::DAY,
+    &day21::DAY,
+    &day22::DAY,
+    &day24::DAY,
+];
+
+const USAGE: &str = "usage: aoc-runner run <DAY|all> [-v] [--inputs DIR] [--check] [--answers FILE]
+
+Runs the solver for DAY, or for every day, on DIR/dayNN.txt (or
+DIR/dayNN.txt.gz), where DIR defaults to $AOC_INPUT_DIR, or `inputs` if
+that is unset. With `all`, days without an input file are skipped.
+
+With --check the answers are compared against FILE, which defaults to
+DIR/answers.toml, and the exit status is nonzero if any answer differs
+or a day with known answers has no input.
+With -v the solvers print debugging output to stderr.";
+
+enum Selection {
+    One(u32),
+    All,
+}
+
+struct Args {
+    selection: Selection,
+    inputs: Inputs,
+    check: bool,
+    answers: Option<PathBuf>,
+    verbose: bool,
+}
+
+impl Args {
+    fn parse<I: IntoIterator<Item = OsString>>(args: I) -> Result<Option<Self>> {
+        let mut args = args.into_iter();
+        let mut inputs = Inputs::from_env();
+        let mut check = false;
+        let mut verbose = false;
+        let mut answers = None;
+        let mut positional = Vec::new();
+        while let Some(arg) = args.next() {
+            match arg.to_str() {
+                Some("--help" | "-h") => return Ok(None),
+                Some("--inputs") => {
+                    inputs = Inputs::new(args.next().context("--inputs needs a directory")?);
+                }
+                Some("--check") => check = true,
+                Some("--verbose" | "-v") => verbose = true,
+                Some("--answers") => {
+                    answers = Some(args.next().context("--answers needs a file")?.into());
+                }
+                Some(flag) if flag.starts_with('-') => bail!("unknown option `{flag}`\n\n{USAGE}"),
+                _ => positional.push(arg),
+            }
+        }
+
+        let selection = match positional.as_slice() {
+            [command, day] if command == "run" => match day.to_str() {
+                Some("all") => Selection::All,
+                Some(day) => Selection::One(
+                    day.parse()
+                        .with_context(|| format!("invalid day `{day}`"))?,
+                ),
+                None => bail!("invalid day {day:?}"),
+            },
+            _ => bail!("expected `run <DAY|all>`\n\n{USAGE}"),
+        };
+        Ok(Some(Args {
+            selection,
+            inputs,
+            check,
+            answers,
+            verbose,
+        }))
+    }
+}
+
+/// One row of the summary table.
+struct Row {
+    day: u32,
+    part1: String,
+    part2: String,
+    time: String,
+    check: String,
+}
+
+impl Row {
+    fn new(day: u32, answers: Option<&Answers>, check: Option<&Check>, note: &str) -> Self {
+        let (part1, part2, time) = match answers {
+            Some(answers) => (
+                answers.part1.clone(),
+                answers.part2.clone(),
+                format_duration(answers.elapsed()),
+            ),
+            None => (note.to_string(), String::new(), String::new()),
+        };
+        Row {
+            day,
+            part1,
+            part2,
+            time,
+            check: check.map_or("", Check::label).to_string(),
+        }
+    }
+}
+
+fn print_table(rows: &[Row], total: Duration, checking: bool) {
+    let width = |f: fn(&Row) -> &str, header: &str| {
+        rows.iter()
+            .map(|r| f(r).chars().count())
+            .fold(header.len(), usize::max)
+    };
+    let w1 = width(|r| &r.part1, "Part 1");
+    let w2 = width(|r| &r.part2, "Part 2");
+    let total = format_duration(total);
+    let wt = width(|r| &r.time, "Time").max(total.chars().count());
+
+    let line = |day: &dyn std::fmt::Display, part1: &str, part2: &str, time: &str, check: &str| {
+        let line = format!("{day:>3}  {part1:w1$}  {part2:w2$}  {time:>wt$}  {check}");
+        println!("{}", line.trim_end());
+    };
+    let check = if checking { "Check" } else { "" };
+    line(&"Day", "Part 1", "Part 2", "Time", check);
+    for row in rows {
+        line(&row.day, &row.part1, &row.part2, &row.time, &row.check);
+    }
+    line(&"", "", "Total", &total, "");
+}
+
+fn run(args: Args) -> Result<bool> {
+    aoc::runner::set_verbose(args.verbose);
+    let days: Vec<&Day> = match args.selection {
+        Selection::All => DAYS.to_vec(),
+        Selection::One(day) => match DAYS.iter().find(|d| d.day == day) {
+            Some(day) => vec![day],
+            None => bail!("there is no solver for day {day}"),
+        },
+    };
+
+    let expected = match (args.check, &args.answers) {
+        (false, _) => None,
+        (true, Some(path)) => Some(Expected::load(path)?),
+        (true, None) => Some(Expected::load(&args.inputs.dir().join("answers.toml"))?),
+    };
+
+    let mut rows = Vec::new();
+    let mut mismatches = Vec::new();
+    let mut total = Duration::ZERO;
+    let mut ok = true;
+    for day in days {
+        let path = match args.inputs.resolve(day.day) {
+            Ok(path) => path,
+            Err(err) if matches!(args.selection, Selection::One(_)) => bail!(err),
+            Err(_) => {
+                let check = (expected.as_ref())
+                    .filter(|expected| expected.has(day.day))
+                    .map(|_| Check::NoInput);
+                ok &= check.is_none();
+                rows.push(Row::new(day.day, None, check.as_ref(), "no input"));
+                continue;
+            }
+        };
+
+        let res = load_input(Some(&path), false).and_then(|input| day.run(input));
+        match res {
+            Ok(answers) => {
+                let check = (expected.as_ref())
+                    .zip(answers.as_ref())
+                    .map(|(expected, answers)| expected.check(day.day, answers));
+                if let Some(answers) = &answers {
+                    total += answers.elapsed();
+                }
+                if let Some(Check::Fail(fails)) = &check {
+                    mismatches.extend(fails.iter().cloned());
+                    ok = false;
+                }
+                rows.push(Row::new(
+                    day.day,
+                    answers.as_ref(),
+                    check.as_ref(),
+                    "no answers",
+                ));
+            }
+            Err(err) => {
+                print_error(&err.context(format!("day {} failed", day.day)));
+                let check = expected.as_ref().map(|_| Check::Error);
+                rows.push(Row::new(day.day, None, check.as_ref(), "failed"));
+                ok = false;
+            }
+        }
+    }
+
+    print_table(&rows, total, expected.is_some());
+    if !mismatches.is_empty() {
+        println!();
+        for mismatch in mismatches {
+            println!("{mismatch}");
+        }
+    }
+    Ok(ok)
+}
+
+fn main() -> ExitCode {
+    let res = Args::parse(std::env::args_os().skip(1)).and_then(|args| match args {
+        Some(args) => run(args),
+        None => {
+            println!("{USAGE}");
+            Ok(true)
+        }
+    });
+    match res {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            print_error(&err);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Times `aoc::fastparse` against the nom parsers in the prelude on
+//! generated inputs. Run with `cargo bench -p aoc`.
+
+use aoc::bench::{Report, Stats};
+use aoc::fastparse;
+use aoc::nom::bytes::complete::is_not;
+use aoc::prelude::*;
+
+use std::hint::black_box;
+use std::time::Instant;
+
+const ITERATIONS: usize = 100;
+
+/// A fixed xorshift sequence, so that every run parses the same input.
+fn random(mut state: u64) -> impl Iterator<Item = u64> {
+    std::iter::from_fn(move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        Some(state)
+    })
+}
+
+fn time<O>(mut f: impl FnMut() -> O) -> Stats {
+    black_box(f());
+    let samples = (0..ITERATIONS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    Stats::new(samples)
+}
+
+/// Checks that both parsers agree, then prints a table of their timings.
+fn compare<O>(name: &str, mut nom: impl FnMut() -> O, mut fast: impl FnMut() -> O)
+where
+    O: PartialEq + fmt::Debug,
+{
+    assert_eq!(nom(), fast(), "the parsers disagree on {name}");
+    println!("\n{name}");
+    let phases = vec![
+        ("nom".to_string(), time(nom)),
+        ("fast".to_string(), time(fast)),
+    ];
+    Report { phases }.print(None);
+}
+
+fn main() {
+    // Like the secrets of day 22.
+    let secrets: String = (random(1).take(100_000))
+        .map(|n| format!("{}\n", n % 16777216))
+        .collect();
+    let large: String = (random(2).take(100_000))
+        .map(|n| format!("{}\n", n >> 1))
+        .collect();
+    // Like the robots of day 14.
+    let robots: String = (random(3).take(50_000))
+        .map(|n| {
+            let field = |shift: u32| (n >> shift & 0xff) as i64 - 128;
+            let (px, py, vx, vy) = (field(0), field(8), field(16), field(24));
+            format!("p={px},{py} v={vx},{vy}\n")
+        })
+        .collect();
+
+    let number_lines = |input: &str| fastparse::int_lines::<u64>(input).unwrap();
+    compare(
+        "one small number per line",
+        || aoc::parse::run(lines(nom_u64), &secrets).unwrap(),
+        || number_lines(&secrets),
+    );
+    compare(
+        "one large number per line",
+        || aoc::parse::run(lines(nom_u64), &large).unwrap(),
+        || number_lines(&large),
+    );
+    compare(
+        "signed integers anywhere",
+        || aoc::parse::run(signed_ints_anywhere::<i64>(), &robots).unwrap(),
+        || fastparse::ints::<i64>(robots.as_bytes()).collect(),
+    );
+    compare(
+        "splitting lines",
+        || {
+            aoc::parse::run(lines(is_not("\r\n")), &robots)
+                .unwrap()
+                .len()
+        },
+        || fastparse::lines(robots.as_bytes()).count(),
+    );
+}
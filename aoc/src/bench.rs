@@ -0,0 +1,187 @@
+//! Repeated timing of a solver, with baselines saved to disk so that runs
+//! can be compared between commits.
+
+use anyhow::{bail, Context, Result};
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::runner::{format_duration, json_string, Day};
+
+/// Where named baselines are stored: `aoc-bench` in `$CARGO_TARGET_DIR`, or
+/// else in the `target` directory of the workspace `day` was built in, so
+/// that it doesn't depend on where the solver is run from.
+fn baseline_dir(day: &Day) -> PathBuf {
+    let target = match std::env::var_os("CARGO_TARGET_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            let crate_dir = Path::new(day.dir);
+            crate_dir.parent().unwrap_or(crate_dir).join("target")
+        }
+    };
+    target.join("aoc-bench")
+}
+
+/// Summary statistics for one phase over all iterations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Computes the statistics of `samples`, which must not be empty.
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+        Stats {
+            min: samples[0],
+            median,
+            p95: samples[(n * 95).div_ceil(100) - 1],
+        }
+    }
+}
+
+/// Parses a `phase min median p95` line of a saved baseline.
+fn parse_line(line: &str) -> Option<(String, Stats)> {
+    let mut fields = line.split(' ');
+    let phase = fields.next()?;
+    let mut nanos = || fields.next()?.parse().ok().map(Duration::from_nanos);
+    let stats = Stats {
+        min: nanos()?,
+        median: nanos()?,
+        p95: nanos()?,
+    };
+    Some((phase.to_string(), stats))
+}
+
+/// The statistics of each phase of a solver, in the order they ran.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    pub phases: Vec<(String, Stats)>,
+}
+
+impl Report {
+    fn path(name: &str, day: &Day) -> PathBuf {
+        baseline_dir(day)
+            .join(name)
+            .join(format!("day{:02}.txt", day.day))
+    }
+
+    /// Saves the report as the baseline `name` for `day`.
+    pub fn save(&self, name: &str, day: &Day) -> Result<()> {
+        let path = Self::path(name, day);
+        let mut text = String::new();
+        for (phase, stats) in &self.phases {
+            writeln!(
+                text,
+                "{phase} {} {} {}",
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.p95.as_nanos()
+            )
+            .unwrap();
+        }
+        std::fs::create_dir_all(path.parent().unwrap())
+            .and_then(|()| std::fs::write(&path, text))
+            .with_context(|| format!("failed to save baseline to {}", path.display()))
+    }
+
+    /// Loads the baseline `name` for `day`.
+    pub fn load(name: &str, day: &Day) -> Result<Self> {
+        let path = Self::path(name, day);
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read baseline {}", path.display()))?;
+        let phases = (text.lines())
+            .map(|line| {
+                parse_line(line)
+                    .with_context(|| format!("malformed line {line:?} in {}", path.display()))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Report { phases })
+    }
+
+    /// Prints a table of the statistics, with the change in median time
+    /// relative to `baseline` if given.
+    pub fn print(&self, baseline: Option<&Report>) {
+        println!(
+            "{:<8} {:>10} {:>10} {:>10}",
+            "Phase", "Min", "Median", "p95"
+        );
+        for (phase, stats) in &self.phases {
+            let mut line = format!(
+                "{phase:<8} {:>10} {:>10} {:>10}",
+                format_duration(stats.min),
+                format_duration(stats.median),
+                format_duration(stats.p95)
+            );
+            let base = baseline.and_then(|b| b.phases.iter().find(|(p, _)| p == phase));
+            if let Some((_, base)) = base {
+                let change = stats.median.as_secs_f64() / base.median.as_secs_f64() - 1.0;
+                write!(
+                    line,
+                    "  {:+.1}% vs {}",
+                    change * 100.0,
+                    format_duration(base.median)
+                )
+                .unwrap();
+            }
+            println!("{line}");
+        }
+    }
+
+    /// Renders the statistics as a JSON object keyed by phase, with times
+    /// in nanoseconds and the median of `baseline` if given.
+    pub fn to_json(&self, baseline: Option<&Report>) -> String {
+        let phases: Vec<_> = (self.phases.iter())
+            .map(|(phase, stats)| {
+                let mut fields = format!(
+                    "\"min_ns\":{},\"median_ns\":{},\"p95_ns\":{}",
+                    stats.min.as_nanos(),
+                    stats.median.as_nanos(),
+                    stats.p95.as_nanos()
+                );
+                let base = baseline.and_then(|b| b.phases.iter().find(|(p, _)| p == phase));
+                if let Some((_, base)) = base {
+                    write!(fields, ",\"baseline_median_ns\":{}", base.median.as_nanos()).unwrap();
+                }
+                format!("{}:{{{fields}}}", json_string(phase))
+            })
+            .collect();
+        format!("{{{}}}", phases.join(","))
+    }
+}
+
+/// Runs `day` on `input` once to warm up and then `iterations` more times,
+/// collecting the statistics of each phase it reports.
+pub fn bench(day: &Day, input: &'static str, iterations: usize) -> Result<Report> {
+    if iterations == 0 {
+        bail!("--bench needs at least one iteration");
+    }
+    day.run(input)?;
+
+    let mut phases: Vec<(String, Vec<Duration>)> = Vec::new();
+    for _ in 0..iterations {
+        let Some(answers) = day.run(input)? else {
+            bail!("the solver reports no answers to time");
+        };
+        for (phase, time) in answers.timings {
+            match phases.iter_mut().find(|(p, _)| p == phase) {
+                Some((_, samples)) => samples.push(time),
+                None => phases.push((phase.to_string(), vec![time])),
+            }
+        }
+    }
+    Ok(Report {
+        phases: (phases.into_iter())
+            .map(|(phase, samples)| (phase, Stats::new(samples)))
+            .collect(),
+    })
+}
@@ -0,0 +1,119 @@
+//! A multiset, for simulations where only the number of each kind of thing
+//! matters.
+
+use std::collections::hash_map::{self, HashMap};
+use std::hash::Hash;
+
+/// How many times each key has been seen.
+///
+/// ```
+/// use aoc::Counter;
+///
+/// let stones: Counter<u64> = [0, 1, 10, 99, 999].into_iter().collect();
+/// // Each kind of stone is mapped once, however many of it there are.
+/// let stones = stones.flat_map_counts(|&n| match n {
+///     0 => vec![(1, 1)],
+///     10 => vec![(1, 1), (0, 1)],
+///     99 => vec![(9, 3)],
+///     n => vec![(n * 2024, 1)],
+/// });
+/// assert_eq!(stones.get(&1), 2);
+/// assert_eq!(stones.total(), 8);
+/// assert_eq!(stones.most_common()[0], (&9, 3));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Counter<K> {
+    counts: HashMap<K, usize>,
+}
+
+impl<K> Default for Counter<K> {
+    fn default() -> Self {
+        Counter {
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq> Counter<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts `key` another `n` times.
+    pub fn add(&mut self, key: K, n: usize) {
+        *self.counts.entry(key).or_default() += n;
+    }
+
+    /// How many times `key` has been counted, which is 0 if never.
+    pub fn get(&self, key: &K) -> usize {
+        self.counts.get(key).copied().unwrap_or_default()
+    }
+
+    /// The number of distinct keys.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// The sum of every key's count.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, usize)> {
+        self.counts.iter().map(|(key, &n)| (key, n))
+    }
+
+    /// Every key with its count, most frequent first.
+    pub fn most_common(&self) -> Vec<(&K, usize)> {
+        let mut keys: Vec<_> = self.iter().collect();
+        keys.sort_unstable_by_key(|&(_, n)| std::cmp::Reverse(n));
+        keys
+    }
+
+    /// Replaces each key with the keys it turns into, given with their
+    /// multiplicity, so that a step of a simulation costs one call per
+    /// distinct key rather than one per item.
+    pub fn flat_map_counts<K2, I, F>(&self, mut f: F) -> Counter<K2>
+    where
+        K2: Hash + Eq,
+        I: IntoIterator<Item = (K2, usize)>,
+        F: FnMut(&K) -> I,
+    {
+        let mut next = Counter::new();
+        for (key, &n) in &self.counts {
+            for (new, mult) in f(key) {
+                next.add(new, n * mult);
+            }
+        }
+        next
+    }
+}
+
+impl<K: Hash + Eq> FromIterator<K> for Counter<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<K: Hash + Eq> Extend<K> for Counter<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.add(key, 1);
+        }
+    }
+}
+
+impl<K> IntoIterator for Counter<K> {
+    type Item = (K, usize);
+    type IntoIter = hash_map::IntoIter<K, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
@@ -0,0 +1,156 @@
+//! Finding where an iterated state machine starts repeating, so that its
+//! state after a huge number of steps can be found without taking them.
+//!
+//! Every function here takes the initial state and a `step` function, and
+//! assumes the states do eventually repeat: if they never do, it doesn't
+//! return.
+//!
+//! ```
+//! use aoc::cycle;
+//!
+//! let step = |&x: &u64| (x * x + 1) % 255;
+//! let cycle = cycle::find(3, step);
+//! assert_eq!(cycle, cycle::floyd(3, step));
+//! assert_eq!(cycle, cycle::hashed(3, step).cycle);
+//!
+//! let slow = (0..1000).fold(3, |x, _| step(&x));
+//! assert_eq!(cycle.nth_state(3, step, 1000), slow);
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The shape of an eventually periodic sequence of states.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    /// The number of steps taken before the first state that repeats.
+    pub mu: usize,
+    /// The number of steps between repeats.
+    pub lambda: usize,
+}
+
+impl Cycle {
+    /// The earliest step with the same state as step `n`.
+    pub fn index(&self, n: usize) -> usize {
+        if n < self.mu {
+            n
+        } else {
+            self.mu + (n - self.mu) % self.lambda
+        }
+    }
+
+    /// The state after `n` steps, taking at most `mu + lambda` of them.
+    pub fn nth_state<S, F>(&self, initial: S, mut step: F, n: usize) -> S
+    where
+        F: FnMut(&S) -> S,
+    {
+        (0..self.index(n)).fold(initial, |state, _| step(&state))
+    }
+}
+
+/// Finds the cycle with Brent's algorithm, which keeps only two states and
+/// takes fewer steps than [`floyd`].
+pub fn find<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    // Search for the cycle length in windows of doubling size.
+    let mut power = 1;
+    let mut lambda = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == lambda {
+            tortoise = hare.clone();
+            power *= 2;
+            lambda = 0;
+        }
+        hare = step(&hare);
+        lambda += 1;
+    }
+
+    // With the hare `lambda` ahead, they meet where the cycle starts.
+    let mut tortoise = initial.clone();
+    let mut hare = (0..lambda).fold(initial, |state, _| step(&state));
+    let mut mu = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+    Cycle { mu, lambda }
+}
+
+/// Finds the cycle with Floyd's algorithm, where the hare takes two steps
+/// for each of the tortoise's.
+pub fn floyd<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let next = step(&hare);
+        hare = step(&next);
+    }
+
+    let mut tortoise = initial;
+    let mut mu = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    let mut lambda = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        lambda += 1;
+    }
+    Cycle { mu, lambda }
+}
+
+/// Every state up to the first repeat, as found by [`hashed`].
+#[derive(Clone, Debug)]
+pub struct History<S> {
+    pub cycle: Cycle,
+    /// The first `mu + lambda` states, from the initial one.
+    pub states: Vec<S>,
+}
+
+impl<S> History<S> {
+    /// The state after `n` steps.
+    pub fn nth_state(&self, n: usize) -> &S {
+        &self.states[self.cycle.index(n)]
+    }
+}
+
+/// Finds the cycle by remembering every state, which takes the fewest
+/// steps and keeps the states for [`History::nth_state`], but needs them
+/// to be hashable and to fit in memory.
+pub fn hashed<S, F>(initial: S, mut step: F) -> History<S>
+where
+    S: Clone + Hash + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+    loop {
+        if let Some(&mu) = seen.get(&state) {
+            let cycle = Cycle {
+                mu,
+                lambda: states.len() - mu,
+            };
+            return History { cycle, states };
+        }
+        seen.insert(state.clone(), states.len());
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
@@ -0,0 +1,219 @@
+//! Byte-oriented integer scanning for large inputs, where the nom parsers in
+//! the prelude spend more time than the solution.
+//!
+//! Apart from [`int_lines`], nothing here reports errors beyond `None`:
+//! callers that care should check lines with [`int`], which must match the
+//! whole slice. Digits are accumulated in a `u64` with wrapping arithmetic,
+//! so a number too large for its type wraps instead of failing.
+//!
+//! ```
+//! use aoc::fastparse;
+//!
+//! let input = b"p=0,4 v=3,-3\r\np=6,3 v=-1,-3\n";
+//! let lines: Vec<_> = fastparse::lines(input).collect();
+//! assert_eq!(lines, [&b"p=0,4 v=3,-3"[..], b"p=6,3 v=-1,-3"]);
+//!
+//! let ints: Vec<i32> = fastparse::ints(lines[1]).collect();
+//! assert_eq!(ints, [6, 3, -1, -3]);
+//! assert_eq!(fastparse::int::<u64>(b"16777216"), Some(16777216));
+//! ```
+
+use crate::parse::ParseReport;
+use nom::Offset;
+
+use std::marker::PhantomData;
+
+/// Integer types that can be scanned. Signed types take a leading `-`.
+pub trait Int: Copy {
+    const SIGNED: bool;
+
+    /// Converts accumulated digits, wrapping if they don't fit.
+    fn from_digits(value: u64, negative: bool) -> Self;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ident),*) => {$(
+        impl Int for $t {
+            const SIGNED: bool = false;
+
+            #[inline]
+            fn from_digits(value: u64, _negative: bool) -> Self {
+                value as $t
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_signed {
+    ($($t:ident),*) => {$(
+        impl Int for $t {
+            const SIGNED: bool = true;
+
+            #[inline]
+            fn from_digits(value: u64, negative: bool) -> Self {
+                let value = value as $t;
+                if negative {
+                    value.wrapping_neg()
+                } else {
+                    value
+                }
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64, usize);
+impl_signed!(i8, i16, i32, i64, isize);
+
+/// The value of eight ASCII digits loaded little-endian, or `None` if any
+/// byte isn't a digit.
+#[inline]
+fn eight_digits(chunk: u64) -> Option<u64> {
+    const ZEROS: u64 = u64::from_ne_bytes([b'0'; 8]);
+    const HIGH: u64 = u64::from_ne_bytes([0xf0; 8]);
+    const SIX: u64 = u64::from_ne_bytes([6; 8]);
+    // Each byte must be 0x30..=0x39: the high nibble is 3, and adding 6
+    // mustn't carry into it. No byte can carry into the next.
+    if chunk & HIGH != ZEROS || (chunk + SIX) & HIGH != ZEROS {
+        return None;
+    }
+    // Combine neighbouring digits, then pairs, then quads.
+    let v = chunk - ZEROS;
+    let v = (v * 10 + (v >> 8)) & 0x00ff_00ff_00ff_00ff;
+    let v = (v * 100 + (v >> 16)) & 0x0000_ffff_0000_ffff;
+    Some((v * 10000 + (v >> 32)) & 0xffff_ffff)
+}
+
+/// The value of the digits at the start of `bytes`, and how many there
+/// were.
+#[inline]
+fn digits(bytes: &[u8]) -> (u64, usize) {
+    let mut value = 0u64;
+    let mut n = 0;
+    while let Some(chunk) = bytes.get(n..n + 8) {
+        let Some(eight) = eight_digits(u64::from_le_bytes(chunk.try_into().unwrap())) else {
+            break;
+        };
+        value = value.wrapping_mul(100_000_000).wrapping_add(eight);
+        n += 8;
+    }
+    while let Some(&byte) = bytes.get(n) {
+        let digit = byte.wrapping_sub(b'0');
+        if digit > 9 {
+            break;
+        }
+        value = value.wrapping_mul(10).wrapping_add(digit as u64);
+        n += 1;
+    }
+    (value, n)
+}
+
+/// Scans the integer at the start of `bytes`, returning it and the number
+/// of bytes it took.
+#[inline]
+pub fn int_prefix<T: Int>(bytes: &[u8]) -> Option<(T, usize)> {
+    let negative = T::SIGNED && bytes.first() == Some(&b'-');
+    let start = negative as usize;
+    let (value, len) = digits(&bytes[start..]);
+    (len > 0).then(|| (T::from_digits(value, negative), start + len))
+}
+
+/// Scans `bytes` as a single integer, with nothing before or after it.
+#[inline]
+pub fn int<T: Int>(bytes: &[u8]) -> Option<T> {
+    match int_prefix(bytes)? {
+        (value, len) if len == bytes.len() => Some(value),
+        _ => None,
+    }
+}
+
+/// Finds every integer in `bytes`, skipping everything else, like
+/// [`signed_ints_anywhere`](crate::parse::signed_ints_anywhere). For signed
+/// types a `-` directly before the digits makes the number negative.
+pub fn ints<T: Int>(bytes: &[u8]) -> Ints<'_, T> {
+    Ints {
+        rest: bytes,
+        _int: PhantomData,
+    }
+}
+
+/// The iterator returned by [`ints`].
+#[derive(Clone, Debug)]
+pub struct Ints<'a, T> {
+    rest: &'a [u8],
+    _int: PhantomData<fn() -> T>,
+}
+
+impl<T: Int> Iterator for Ints<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        let start = self.rest.iter().position(u8::is_ascii_digit)?;
+        let negative = T::SIGNED && start > 0 && self.rest[start - 1] == b'-';
+        let (value, len) = digits(&self.rest[start..]);
+        self.rest = &self.rest[start + len..];
+        Some(T::from_digits(value, negative))
+    }
+}
+
+/// Splits `bytes` into lines, like [`str::lines`]: a `\r` before each
+/// `\n` is dropped, and a final line needs no line ending. Line endings
+/// are found with `memchr`, which searches many bytes at once.
+pub fn lines(bytes: &[u8]) -> Lines<'_> {
+    Lines { rest: bytes }
+}
+
+/// The iterator returned by [`lines`].
+#[derive(Clone, Debug)]
+pub struct Lines<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a [u8];
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a [u8]> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let line = match memchr::memchr(b'\n', self.rest) {
+            Some(end) => {
+                let line = &self.rest[..end];
+                self.rest = &self.rest[end + 1..];
+                line
+            }
+            None => std::mem::take(&mut self.rest),
+        };
+        Some(line.strip_suffix(b"\r").unwrap_or(line))
+    }
+}
+
+/// Scans an input of one integer per line, such as day 22's. Like
+/// [`lines`](crate::parse::lines) it allows trailing spaces and blank lines
+/// at the end, and anything else is reported with where it was found.
+///
+/// ```
+/// let secrets = aoc::fastparse::int_lines::<u64>("1\r\n10 \n100\n\n").unwrap();
+/// assert_eq!(secrets, [1, 10, 100]);
+///
+/// let err = aoc::fastparse::int_lines::<u64>("1\n2x\n3\n").unwrap_err();
+/// assert_eq!((err.line, err.column), (2, 2));
+/// ```
+pub fn int_lines<T: Int>(input: &str) -> Result<Vec<T>, ParseReport> {
+    let body = input.trim_end();
+    (lines(body.as_bytes()))
+        .map(|line| {
+            let start = body.as_bytes().offset(line);
+            let at = |len: usize| &body[start + len..];
+            match int_prefix(line) {
+                None => Err(ParseReport::new(input, at(0), "expected a number")),
+                Some((value, len)) if line[len..].iter().all(|&b| b == b' ' || b == b'\t') => {
+                    Ok(value)
+                }
+                Some((_, len)) => Err(ParseReport::new(input, at(len), "expected a line ending")),
+            }
+        })
+        .collect()
+}
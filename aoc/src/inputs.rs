@@ -0,0 +1,117 @@
+//! Finds puzzle inputs in a local directory, so that they don't have to be
+//! passed by hand.
+//!
+//! The directory is named by the `AOC_INPUT_DIR` environment variable, or is
+//! `inputs` if it is unset, and each day's real input is `dayNN.txt`. The
+//! examples are checked in with each day's crate instead, as
+//! `examples/dayNN-K.txt` numbered from 1, which is where `example_test!`
+//! reads them from too. Any of them may be gzipped instead, with a `.gz`
+//! suffix. Nothing is ever downloaded.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// The environment variable naming the input directory.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Which of a day's inputs to use.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Slot {
+    /// The personal puzzle input.
+    #[default]
+    Real,
+    /// One of the examples from the puzzle text, numbered from 1.
+    Example(u32),
+}
+
+impl fmt::Display for Slot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Slot::Real => write!(f, "input"),
+            Slot::Example(n) => write!(f, "example {n}"),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error(
+    "no {slot} for day {day}: expected {} or {0}.gz{}",
+    path.display(),
+    if *chosen { "" } else { "\n(set AOC_INPUT_DIR to read inputs from another directory)" }
+)]
+pub struct MissingInput {
+    pub day: u32,
+    pub slot: Slot,
+    pub path: PathBuf,
+    chosen: bool,
+}
+
+/// A directory of cached inputs.
+#[derive(Clone, Debug)]
+pub struct Inputs {
+    dir: PathBuf,
+    /// Whether the directory was picked by the user rather than defaulted.
+    chosen: bool,
+}
+
+impl Inputs {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Inputs {
+            dir: dir.into(),
+            chosen: true,
+        }
+    }
+
+    /// The directory named by `AOC_INPUT_DIR`, or `inputs`.
+    pub fn from_env() -> Self {
+        match std::env::var_os(INPUT_DIR_VAR) {
+            Some(dir) => Inputs::new(dir),
+            None => Inputs {
+                dir: PathBuf::from("inputs"),
+                chosen: false,
+            },
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Where the input should be stored, uncompressed.
+    pub fn path(&self, day: u32) -> PathBuf {
+        self.dir.join(format!("day{day:02}.txt"))
+    }
+
+    /// Finds the input, preferring the uncompressed file.
+    pub fn find(&self, day: u32) -> Option<PathBuf> {
+        find_file(self.path(day))
+    }
+
+    /// Finds the input, or explains where it should have been.
+    pub fn resolve(&self, day: u32) -> Result<PathBuf, MissingInput> {
+        self.find(day).ok_or_else(|| MissingInput {
+            day,
+            slot: Slot::Real,
+            path: self.path(day),
+            chosen: self.chosen,
+        })
+    }
+}
+
+fn find_file(path: PathBuf) -> Option<PathBuf> {
+    let mut gz = path.clone().into_os_string();
+    gz.push(".gz");
+    [path, gz.into()].into_iter().find(|p| p.exists())
+}
+
+/// Finds example `n` of `day` in the crate at `crate_dir`, or explains
+/// where it should have been.
+pub fn resolve_example(crate_dir: &Path, day: u32, n: u32) -> Result<PathBuf, MissingInput> {
+    let path = crate_dir.join(format!("examples/day{day:02}-{n}.txt"));
+    find_file(path.clone()).ok_or(MissingInput {
+        day,
+        slot: Slot::Example(n),
+        path,
+        chosen: true,
+    })
+}
@@ -1,14 +1,42 @@
 pub mod astar;
+pub mod bench;
+pub mod counter;
+pub mod cycle;
+pub mod fastparse;
+pub mod inputs;
+pub mod linalg;
 pub mod map;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod runner;
+pub mod solver;
+pub mod style;
 pub mod types;
+pub mod viz;
+
+pub use counter::Counter;
+pub use nom;
+pub use nom_language;
+pub use runner::verbose;
+pub use solver::Solver;
 
 pub mod prelude {
+    pub use super::counter::Counter;
     pub use super::map::{Coords, DisplayTile, Map, ParseTile};
+    pub use super::memo::Memo;
+    pub use super::parse::{blocks, lines, number_grid, numbers, signed_ints_anywhere};
+    pub use super::parse::{AocParse, Complete, PResult};
+    pub use super::runner::Solution;
+    pub use super::solver::Solver;
     pub use super::types::Dir;
+    pub use crate::{example_test, vprintln};
     #[cfg(feature = "derive")]
-    pub use aoc_macros::{main, DisplayTile, ParseTile};
+    pub use aoc_macros::{main, AocParse, DisplayTile, ParseTile};
 
-    pub use nom::branch::alt;
+    pub use super::parse::compat::{alt, map, map_res, opt, recognize, value};
+    pub use super::parse::compat::{delimited, pair, preceded, separated_pair, terminated, tuple};
+    pub use super::parse::compat::{many0, many1, separated_list0, separated_list1};
     pub use nom::bytes::complete::{tag, take_while_m_n};
     pub use nom::character::complete::{alpha0, alpha1, alphanumeric0, alphanumeric1, anychar};
     pub use nom::character::complete::{char as nom_char, line_ending, newline, space0, space1};
@@ -18,11 +46,10 @@ pub mod prelude {
     pub use nom::character::complete::{
         u16 as nom_u16, u32 as nom_u32, u64 as nom_u64, u8 as nom_u8,
     };
-    pub use nom::combinator::{eof, iterator, map, map_res, opt, recognize, value};
+    pub use nom::combinator::{eof, iterator};
     pub use nom::error::Error as NomError;
-    pub use nom::multi::{many0, many1, separated_list0, separated_list1};
-    pub use nom::sequence::{delimited, pair, preceded, separated_pair, terminated, tuple};
-    pub use nom::IResult;
+    pub use nom::{IResult, Parser};
+    pub use nom_language::error::VerboseError;
 
     pub use smallvec::SmallVec;
 
@@ -37,8 +64,4 @@ pub mod prelude {
         stdin().read_to_string(&mut buf).unwrap();
         buf
     }
-
-    pub fn nom_err<T>(r: IResult<&str, T>) -> IResult<&str, T> {
-        r
-    }
 }
@@ -0,0 +1,322 @@
+//! Exact solutions of small systems of linear equations with integer
+//! coefficients.
+//!
+//! ```
+//! use aoc::linalg::{self, Rational, Solutions};
+//!
+//! // x + 2y = 5, 3x + 4y = 6
+//! let solutions = linalg::solve([[1, 2], [3, 4]], [5, 6]);
+//! assert_eq!(solutions, Solutions::Unique([Rational::from(-4), Rational::new(9, 2)]));
+//!
+//! // The same line twice has a solution for every point on it.
+//! let Solutions::Family { particular, directions } = linalg::solve([[1, 2], [2, 4]], [5, 10])
+//! else {
+//!     panic!("expected a family of solutions");
+//! };
+//! assert_eq!(particular, [5.into(), 0.into()]);
+//! assert_eq!(directions, [[(-2).into(), 1.into()]]);
+//! ```
+
+use crate::math;
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// A fraction in lowest terms, with a positive denominator.
+///
+/// Arithmetic panics if a numerator or denominator would overflow an
+/// `i128`, rather than silently giving a wrong answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+fn checked(value: Option<i128>) -> i128 {
+    value.expect("overflow in rational arithmetic")
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
+
+    /// The fraction `num / den`, which panics if `den` is zero.
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "a rational cannot have a zero denominator");
+        let g = gcd(num, den) * den.signum();
+        Rational {
+            num: num / g,
+            den: den / g,
+        }
+    }
+
+    pub fn numer(self) -> i128 {
+        self.num
+    }
+
+    pub fn denom(self) -> i128 {
+        self.den
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.num == 0
+    }
+
+    /// The value as an integer, if it is one.
+    pub fn to_integer(self) -> Option<i128> {
+        (self.den == 1).then_some(self.num)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Rational {
+            num: value.into(),
+            den: 1,
+        }
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Rational) -> Rational {
+        let g = gcd(self.den, rhs.den);
+        let den = checked((self.den / g).checked_mul(rhs.den));
+        let lhs = checked(self.num.checked_mul(rhs.den / g));
+        let rhs = checked(rhs.num.checked_mul(self.den / g));
+        Rational::new(checked(lhs.checked_add(rhs)), den)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Rational) -> Rational {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Rational) -> Rational {
+        // Cancel first to keep the products small.
+        let g1 = gcd(self.num, rhs.den);
+        let g2 = gcd(rhs.num, self.den);
+        Rational::new(
+            checked((self.num / g1).checked_mul(rhs.num / g2)),
+            checked((self.den / g2).checked_mul(rhs.den / g1)),
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, rhs: Rational) -> Rational {
+        assert!(!rhs.is_zero(), "division of a rational by zero");
+        self * Rational::new(rhs.den, rhs.num)
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        let lhs = checked(self.num.checked_mul(other.den));
+        let rhs = checked(other.num.checked_mul(self.den));
+        lhs.cmp(&rhs)
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The solutions of a system of linear equations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Solutions<const N: usize> {
+    /// The equations contradict each other.
+    None,
+    Unique([Rational; N]),
+    /// Every `particular + t1 * directions[0] + t2 * directions[1] + ...`
+    /// is a solution, with one direction for each variable that the
+    /// equations leave free.
+    Family {
+        particular: [Rational; N],
+        directions: Vec<[Rational; N]>,
+    },
+}
+
+/// Solves `a x = b` by Gaussian elimination, where `a[i]` holds the
+/// coefficients of equation `i`.
+pub fn solve<const N: usize>(a: [[i64; N]; N], b: [i64; N]) -> Solutions<N> {
+    // The augmented matrix, reduced to row echelon form in place.
+    let mut rows: Vec<Vec<Rational>> = (a.iter().zip(b))
+        .map(|(row, b)| row.iter().chain([&b]).map(|&v| v.into()).collect())
+        .collect();
+    let mut pivots = Vec::new();
+    for col in 0..N {
+        let rank = pivots.len();
+        let Some(pivot) = (rank..N).find(|&r| !rows[r][col].is_zero()) else {
+            continue;
+        };
+        rows.swap(rank, pivot);
+        let scale = rows[rank][col];
+        for v in &mut rows[rank] {
+            *v = *v / scale;
+        }
+        // Every row at or below `rank` is zero before `col`, so the whole
+        // pivot row can be subtracted.
+        let pivot_row = rows[rank].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            let factor = row[col];
+            if r != rank && !factor.is_zero() {
+                for (v, &p) in row.iter_mut().zip(&pivot_row) {
+                    *v = *v - factor * p;
+                }
+            }
+        }
+        pivots.push(col);
+    }
+
+    let rank = pivots.len();
+    if rows[rank..].iter().any(|row| !row[N].is_zero()) {
+        return Solutions::None;
+    }
+    let mut particular = [Rational::ZERO; N];
+    for (row, &col) in pivots.iter().enumerate() {
+        particular[col] = rows[row][N];
+    }
+    if rank == N {
+        return Solutions::Unique(particular);
+    }
+    let directions = (0..N)
+        .filter(|col| !pivots.contains(col))
+        .map(|free| {
+            let mut direction = [Rational::ZERO; N];
+            direction[free] = Rational::ONE;
+            for (row, &col) in pivots.iter().enumerate() {
+                direction[col] = -rows[row][free];
+            }
+            direction
+        })
+        .collect();
+    Solutions::Family {
+        particular,
+        directions,
+    }
+}
+
+/// Finds the non-negative integer solution of `a x = b` with the least
+/// `cost[0] * x[0] + cost[1] * x[1]`, even when the equations are
+/// dependent. There is none if no solution is a non-negative integer, or if
+/// the cost can be made as low as you like.
+///
+/// ```
+/// use aoc::linalg;
+///
+/// // A button moving (94, 34) for 3 tokens and one moving (22, 67) for 1.
+/// let presses = linalg::min_cost_solution([[94, 22], [34, 67]], [8400, 5400], [3, 1]);
+/// assert_eq!(presses, Some([80, 40]));
+///
+/// // Buttons moving (2, 2) and (3, 3) both reach (12, 12), cheapest with
+/// // four presses of the second.
+/// let presses = linalg::min_cost_solution([[2, 3], [2, 3]], [12, 12], [3, 1]);
+/// assert_eq!(presses, Some([0, 4]));
+/// ```
+pub fn min_cost_solution(a: [[i64; 2]; 2], b: [i64; 2], cost: [i64; 2]) -> Option<[i64; 2]> {
+    match solve(a, b) {
+        Solutions::None => None,
+        Solutions::Unique(x) => {
+            let x = x.map(|v| v.to_integer().and_then(|v| i64::try_from(v).ok()));
+            match x {
+                [Some(x0 @ 0..), Some(x1 @ 0..)] => Some([x0, x1]),
+                _ => None,
+            }
+        }
+        Solutions::Family { .. } => {
+            // The equations are multiples of one line, or all zero.
+            let Some(row) = (0..2).find(|&r| a[r] != [0, 0]) else {
+                return (cost[0] >= 0 && cost[1] >= 0).then_some([0, 0]);
+            };
+            min_cost_on_line(a[row], b[row], cost)
+        }
+    }
+}
+
+/// The non-negative integer point on `c[0] x + c[1] y = r` with the least
+/// cost, where `c` isn't zero.
+fn min_cost_on_line(c: [i64; 2], r: i64, cost: [i64; 2]) -> Option<[i64; 2]> {
+    let (g, u, v) = math::egcd(c[0], c[1]);
+    if r % g != 0 {
+        return None;
+    }
+    // Every solution is (x0 + sx t, y0 - sy t) for integer t.
+    let k = i128::from(r / g);
+    let (x0, y0) = (i128::from(u) * k, i128::from(v) * k);
+    let (sx, sy) = (i128::from(c[1] / g), i128::from(c[0] / g));
+
+    // The range of t where both coordinates are non-negative.
+    let (mut lo, mut hi) = (None::<i128>, None::<i128>);
+    for (start, step) in [(x0, sx), (y0, -sy)] {
+        match step.cmp(&0) {
+            // start + step t >= 0
+            Ordering::Greater => {
+                let bound = -start.div_euclid(step);
+                lo = Some(lo.map_or(bound, |lo| lo.max(bound)));
+            }
+            Ordering::Less => {
+                let bound = start.div_euclid(-step);
+                hi = Some(hi.map_or(bound, |hi| hi.min(bound)));
+            }
+            Ordering::Equal if start < 0 => return None,
+            Ordering::Equal => {}
+        }
+    }
+    if let (Some(lo), Some(hi)) = (lo, hi) {
+        if lo > hi {
+            return None;
+        }
+    }
+
+    let slope = i128::from(cost[0]) * sx - i128::from(cost[1]) * sy;
+    let t = match slope.cmp(&0) {
+        Ordering::Greater => lo?,
+        Ordering::Less => hi?,
+        Ordering::Equal => lo.or(hi).unwrap_or(0),
+    };
+    let x = i64::try_from(x0 + sx * t).ok()?;
+    let y = i64::try_from(y0 - sy * t).ok()?;
+    Some([x, y])
+}
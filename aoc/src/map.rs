@@ -1,15 +1,21 @@
+use crate::style::Style;
 use crate::types::Dir;
 use nom::bytes::complete::take_while1;
 use nom::character::complete::newline;
 use nom::combinator::iterator;
-use nom::error::{ErrorKind, FromExternalError, ParseError};
+use nom::error::{ContextError, ErrorKind, FromExternalError, ParseError};
 use nom::sequence::terminated;
-use nom::{AsChar, IResult, InputIter, InputLength, InputTakeAtPosition, Slice};
+use nom::{AsChar, IResult, Input};
 use smallvec::SmallVec;
 
+use std::collections::HashSet;
 use std::fmt;
 use std::num::NonZeroUsize;
-use std::ops::{Index, IndexMut, RangeFrom};
+use std::ops::{Index, IndexMut};
+
+mod formats;
+
+pub use formats::{FormatError, GrayLevel};
 
 pub trait ParseTile: Copy + Eq {
     fn from_char(ch: char) -> Option<Self>;
@@ -17,6 +23,11 @@ pub trait ParseTile: Copy + Eq {
 
 pub trait DisplayTile: Copy + Eq {
     fn to_char(self) -> char;
+
+    /// How the tile is drawn when rendering in colour, see [`Map::render`].
+    fn to_style(self) -> Style {
+        Style::PLAIN
+    }
 }
 
 impl ParseTile for char {
@@ -136,6 +147,15 @@ impl<T> Map<T> {
     pub fn iter(&self) -> MapIterator<'_, T> {
         MapIterator { map: self, idx: 0 }
     }
+
+    /// A map of the same size with `f` applied to each tile, for example to
+    /// turn walls into `true` for [`Map::to_pbm`].
+    pub fn map_tiles<U>(&self, f: impl FnMut(&T) -> U) -> Map<U> {
+        Map {
+            _width: self._width,
+            tiles: self.tiles.iter().map(f).collect(),
+        }
+    }
 }
 
 pub struct MapIterator<'a, T> {
@@ -213,27 +233,136 @@ impl<T> IndexMut<Coords> for Map<T> {
 
 impl<T: DisplayTile> fmt::Display for Map<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for y in 0..self.height() {
+        self.render().fmt(f)
+    }
+}
+
+/// What to draw over a tile in an overlay: a replacement character, a
+/// style, or both.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Mark {
+    pub ch: Option<char>,
+    pub style: Option<Style>,
+}
+
+impl From<char> for Mark {
+    fn from(ch: char) -> Self {
+        Mark {
+            ch: Some(ch),
+            style: None,
+        }
+    }
+}
+
+impl From<Style> for Mark {
+    fn from(style: Style) -> Self {
+        Mark {
+            ch: None,
+            style: Some(style),
+        }
+    }
+}
+
+impl From<(char, Style)> for Mark {
+    fn from((ch, style): (char, Style)) -> Self {
+        Mark {
+            ch: Some(ch),
+            style: Some(style),
+        }
+    }
+}
+
+/// A `Map` ready to be displayed, optionally in colour and with some tiles
+/// marked.
+pub struct Render<'a, T> {
+    map: &'a Map<T>,
+    overlay: Option<(&'a HashSet<Coords>, Mark)>,
+    styled: bool,
+}
+
+impl<'a, T: DisplayTile> Render<'a, T> {
+    /// Draws each tile with its [`DisplayTile::to_style`] using ANSI escape
+    /// codes, if `styled` is true.
+    pub fn styled(self, styled: bool) -> Self {
+        Render { styled, ..self }
+    }
+
+    /// Draws `mark` over the tiles at `coords`.
+    pub fn with_overlay(self, coords: &'a HashSet<Coords>, mark: impl Into<Mark>) -> Self {
+        Render {
+            overlay: Some((coords, mark.into())),
+            ..self
+        }
+    }
+
+    fn tile(&self, coords: Coords) -> (char, Style) {
+        let tile = self.map[coords];
+        match self.overlay {
+            Some((marked, mark)) if marked.contains(&coords) => (
+                mark.ch.unwrap_or_else(|| tile.to_char()),
+                mark.style.unwrap_or_else(|| tile.to_style()),
+            ),
+            _ => (tile.to_char(), tile.to_style()),
+        }
+    }
+}
+
+impl<T: DisplayTile> fmt::Display for Render<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in 0..self.map.height() {
             if y > 0 {
-                write!(f, "\n")?;
+                writeln!(f)?;
+            }
+            let mut current = Style::PLAIN;
+            for x in 0..self.map.width() {
+                let (ch, style) = self.tile((x, y));
+                if self.styled && style != current {
+                    style.write_ansi(f)?;
+                    current = style;
+                }
+                write!(f, "{}", ch)?;
             }
-            for x in 0..self.width() {
-                write!(f, "{}", T::to_char(self[(x, y)]))?;
+            if !current.is_plain() {
+                Style::PLAIN.write_ansi(f)?;
             }
         }
         Ok(())
     }
 }
 
+impl<T: DisplayTile> Map<T> {
+    /// Displays the map, as plain text unless asked for colour.
+    pub fn render(&self) -> Render<'_, T> {
+        Render {
+            map: self,
+            overlay: None,
+            styled: false,
+        }
+    }
+
+    /// Displays the map with `mark` drawn over the tiles at `coords`, such as
+    /// a path through a maze, without changing the map.
+    pub fn render_with_overlay<'a>(
+        &'a self,
+        coords: &'a HashSet<Coords>,
+        mark: impl Into<Mark>,
+    ) -> Render<'a, T> {
+        self.render().with_overlay(coords, mark)
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 #[error("expected a row of length {0}, got {1} characters")]
 pub struct InvalidLength(NonZeroUsize, usize);
 
+/// The context of a row with the wrong length in [`Map::parse`].
+const MAP_ROW: &str = "map row, which must be as wide as the first";
+
 impl<T: ParseTile> Map<T> {
     pub fn push_row<I>(&mut self, input: I) -> Result<(), InvalidLength>
     where
-        I: InputIter,
-        <I as InputIter>::Item: AsChar,
+        I: Input,
+        <I as Input>::Item: AsChar,
     {
         let len = input.iter_elements().count();
         if let Some(exp) = self._width {
@@ -253,22 +382,29 @@ impl<T: ParseTile> Map<T> {
         Ok(())
     }
 
+    /// Parses rows of tiles, each ending with a newline, from a `&str` or
+    /// a `&[u8]`.
+    ///
+    /// ```
+    /// use aoc::prelude::*;
+    ///
+    /// let (_, map) = Map::<char>::parse::<_, NomError<_>>(&b"#.\n.#\n"[..]).unwrap();
+    /// assert_eq!(map.height(), 2);
+    /// ```
     pub fn parse<I, E>(input: I) -> IResult<I, Self, E>
     where
-        I: InputIter + InputLength + Clone,
-        I: InputTakeAtPosition<Item = char> + Slice<RangeFrom<usize>>,
-        <I as InputIter>::Item: AsChar,
-        E: ParseError<I>,
+        I: Input,
+        <I as Input>::Item: AsChar,
+        E: ParseError<I> + ContextError<I>,
         E: FromExternalError<I, InvalidLength>,
     {
         let mut map = Self::default();
-        let mut iter = iterator(
-            input,
-            terminated(take_while1(|ch| T::from_char(ch).is_some()), newline),
-        );
+        let is_tile = |ch: <I as Input>::Item| T::from_char(ch.as_char()).is_some();
+        let mut iter = iterator(input, terminated(take_while1(is_tile), newline));
         for row in &mut iter {
             map.push_row(row.clone()).map_err(|e| {
-                nom::Err::Error(E::from_external_error(row, ErrorKind::TakeWhile1, e))
+                let err = E::from_external_error(row.clone(), ErrorKind::Verify, e);
+                nom::Err::Error(E::add_context(row, MAP_ROW, err))
             })?
         }
         Ok((iter.finish()?.0, map))
@@ -276,12 +412,8 @@ impl<T: ParseTile> Map<T> {
 
     pub fn parse_nom<I>(input: I) -> IResult<I, Self>
     where
-        I: InputIter + InputLength + Clone,
-        I: InputTakeAtPosition<Item = char> + Slice<RangeFrom<usize>>,
-        <I as InputIter>::Item: AsChar,
-        //I: nom::UnspecializedInput + nom::InputTake + nom::InputLength,
-        //I: nom::Slice<std::ops::RangeFrom<usize>> + Clone,
-        //I: nom::InputIter + InputLength,
+        I: Input,
+        <I as Input>::Item: AsChar,
     {
         Self::parse(input)
     }
@@ -0,0 +1,458 @@
+//! Ways of storing a `Map` other than its text form: a compact binary
+//! encoding, PBM and PGM images, and serde (with the `serde` feature).
+
+use super::{DisplayTile, Map, ParseTile};
+
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+
+#[derive(thiserror::Error, Debug)]
+pub enum FormatError {
+    #[error("not a {0} file")]
+    BadMagic(&'static str),
+    #[error("unexpected end of data")]
+    Truncated,
+    #[error("invalid header: {0}")]
+    BadHeader(&'static str),
+    #[error("{0} bytes of trailing data")]
+    Trailing(usize),
+    #[error("tile character {0:?} is not valid for this map")]
+    BadTile(char),
+    #[error("tile {0:?} cannot be parsed back from its character")]
+    Unparsable(char),
+    #[error("tile index {0} is outside the palette")]
+    BadIndex(usize),
+    #[error("value {0} does not fit in the tile type")]
+    BadLevel(u64),
+    #[error("value {0} is too large for a PGM image")]
+    TooLarge(u64),
+}
+
+fn from_parts<T>(width: usize, tiles: Vec<T>) -> Map<T> {
+    Map {
+        _width: NonZeroUsize::new(width),
+        tiles,
+    }
+}
+
+/// The number of tiles in a map of the given size, which is checked as it
+/// comes from an untrusted header.
+fn area(width: usize, height: usize) -> Result<usize, FormatError> {
+    width
+        .checked_mul(height)
+        .ok_or(FormatError::BadHeader("size too large"))
+}
+
+/// The width and height of a map, which may be empty.
+fn size<T>(map: &Map<T>) -> (usize, usize) {
+    match map._width {
+        Some(_) => (map.width(), map.height()),
+        None => (0, 0),
+    }
+}
+
+const MAGIC: &[u8; 4] = b"AOCM";
+
+/// Reads little-endian integers from the front of a byte slice.
+struct Bytes<'a>(&'a [u8]);
+
+impl<'a> Bytes<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], FormatError> {
+        if self.0.len() < len {
+            return Err(FormatError::Truncated);
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32, FormatError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+}
+
+impl<T: DisplayTile + ParseTile> Map<T> {
+    /// Encodes the map compactly, as its size, the distinct characters of its
+    /// tiles, and then each tile as an index into those using as few bits as
+    /// possible. A map of walls and spaces takes one bit per tile.
+    ///
+    /// Tiles are decoded with [`ParseTile::from_char`], so a map holding a
+    /// tile that doesn't parse back from its character, such as one marked
+    /// `#[tile(.., skip)]`, can't be encoded.
+    ///
+    /// ```
+    /// use aoc::map::FormatError;
+    /// use aoc::prelude::*;
+    ///
+    /// let map = aoc::parse::run(Map::<char>::parse, "#..\n.#.\n..@\n").unwrap();
+    /// let bytes = map.to_bytes().unwrap();
+    /// assert_eq!(Map::from_bytes(&bytes).unwrap(), map);
+    /// let cut = Map::<char>::from_bytes(&bytes[..bytes.len() - 1]);
+    /// assert!(matches!(cut, Err(FormatError::Truncated)));
+    ///
+    /// #[derive(Clone, Copy, Debug, PartialEq, Eq, DisplayTile, ParseTile)]
+    /// enum Tile {
+    ///     #[tile('.')]
+    ///     Empty,
+    ///     #[tile('O', skip)]
+    ///     Path,
+    /// }
+    /// let map = Map::new(2, 1, Tile::Path);
+    /// assert!(matches!(map.to_bytes(), Err(FormatError::Unparsable('O'))));
+    /// ```
+    pub fn to_bytes(&self) -> Result<Vec<u8>, FormatError> {
+        let (width, height) = size(self);
+        let mut palette = Vec::new();
+        let mut index = HashMap::new();
+        for tile in &self.tiles {
+            let ch = tile.to_char();
+            if T::from_char(ch) != Some(*tile) {
+                return Err(FormatError::Unparsable(ch));
+            }
+            index.entry(ch).or_insert_with(|| {
+                palette.push(ch);
+                palette.len() - 1
+            });
+        }
+        let bits = bits_for(palette.len());
+
+        let mut out = MAGIC.to_vec();
+        out.extend((width as u32).to_le_bytes());
+        out.extend((height as u32).to_le_bytes());
+        out.extend((palette.len() as u32).to_le_bytes());
+        for ch in &palette {
+            out.extend((*ch as u32).to_le_bytes());
+        }
+        let mut packed = vec![0u8; (self.tiles.len() * bits).div_ceil(8)];
+        for (n, tile) in self.tiles.iter().enumerate() {
+            let idx = index[&tile.to_char()];
+            for bit in 0..bits {
+                if idx & (1 << bit) != 0 {
+                    let pos = n * bits + bit;
+                    packed[pos / 8] |= 1 << (pos % 8);
+                }
+            }
+        }
+        out.extend(packed);
+        Ok(out)
+    }
+
+    /// Decodes a map written by [`Map::to_bytes`].
+    pub fn from_bytes(data: &[u8]) -> Result<Self, FormatError> {
+        let mut data = Bytes(data);
+        if data.take(4).ok() != Some(MAGIC) {
+            return Err(FormatError::BadMagic("binary map"));
+        }
+        let width = data.u32()? as usize;
+        let height = data.u32()? as usize;
+        let palette = (0..data.u32()?)
+            .map(|_| {
+                let ch = char::from_u32(data.u32()?).ok_or(FormatError::BadHeader("bad char"))?;
+                T::from_char(ch).ok_or(FormatError::BadTile(ch))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let bits = bits_for(palette.len());
+        let len = area(width, height)?;
+        let packed = data.take(area(len, bits)?.div_ceil(8))?;
+        if !data.0.is_empty() {
+            return Err(FormatError::Trailing(data.0.len()));
+        }
+
+        let tiles = (0..len)
+            .map(|n| {
+                let idx = (0..bits)
+                    .filter(|bit| {
+                        let pos = n * bits + bit;
+                        packed[pos / 8] & (1 << (pos % 8)) != 0
+                    })
+                    .fold(0, |idx, bit| idx | (1 << bit));
+                palette.get(idx).copied().ok_or(FormatError::BadIndex(idx))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(from_parts(width, tiles))
+    }
+}
+
+/// The number of bits needed to tell `n` things apart.
+fn bits_for(n: usize) -> usize {
+    (usize::BITS - n.saturating_sub(1).leading_zeros()) as usize
+}
+
+/// Reads the whitespace separated header of a netpbm image, skipping
+/// comments.
+struct Netpbm<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Netpbm<'a> {
+    fn skip_space(&mut self) {
+        while let Some(&b) = self.data.get(self.pos) {
+            if b == b'#' {
+                while self.data.get(self.pos).is_some_and(|b| *b != b'\n') {
+                    self.pos += 1;
+                }
+            } else if b.is_ascii_whitespace() {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<u64, FormatError> {
+        self.skip_space();
+        let start = self.pos;
+        while self.data.get(self.pos).is_some_and(u8::is_ascii_digit) {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.data[start..self.pos])
+            .unwrap()
+            .parse()
+            .map_err(|_| match self.data.get(self.pos) {
+                None => FormatError::Truncated,
+                Some(_) => FormatError::BadHeader("expected a number"),
+            })
+    }
+
+    /// Reads the magic number and size, returning whether the image is in
+    /// the raw (binary) form.
+    fn header(
+        &mut self,
+        plain: &[u8; 2],
+        raw: &[u8; 2],
+        kind: &'static str,
+    ) -> Result<(bool, usize, usize), FormatError> {
+        let magic = self.data.get(..2);
+        let is_raw = match magic {
+            Some(m) if m == raw => true,
+            Some(m) if m == plain => false,
+            _ => return Err(FormatError::BadMagic(kind)),
+        };
+        self.pos = 2;
+        let width = self.number()? as usize;
+        let height = self.number()? as usize;
+        Ok((is_raw, width, height))
+    }
+
+    /// The pixel data of a raw image, which follows a single whitespace
+    /// character after the header.
+    fn raw(&self, len: usize) -> Result<&'a [u8], FormatError> {
+        (self.data.get(self.pos + 1..))
+            .and_then(|data| data.get(..len))
+            .ok_or(FormatError::Truncated)
+    }
+}
+
+impl Map<bool> {
+    /// Encodes the map as a binary PBM image, with `true` tiles black.
+    ///
+    /// ```
+    /// use aoc::map::{FormatError, Map};
+    ///
+    /// // Rows are padded to a whole number of bytes.
+    /// let mut map = Map::new(10, 3, false);
+    /// map[(9, 0)] = true;
+    /// map[(0, 2)] = true;
+    /// let pbm = map.to_pbm();
+    /// assert_eq!(Map::from_pbm(&pbm).unwrap(), map);
+    /// let cut = Map::from_pbm(&pbm[..pbm.len() - 1]);
+    /// assert!(matches!(cut, Err(FormatError::Truncated)));
+    ///
+    /// let plain = Map::from_pbm(b"P1\n# a comment\n3 2\n1 0 0\n011\n").unwrap();
+    /// assert_eq!(plain.tiles, [true, false, false, false, true, true]);
+    /// ```
+    pub fn to_pbm(&self) -> Vec<u8> {
+        let (width, height) = size(self);
+        let mut out = format!("P4\n{width} {height}\n").into_bytes();
+        for row in self.tiles.chunks(width.max(1)) {
+            for byte in row.chunks(8) {
+                let bits =
+                    (byte.iter().enumerate()).fold(0u8, |acc, (n, b)| acc | (*b as u8) << (7 - n));
+                out.push(bits);
+            }
+        }
+        out
+    }
+
+    /// Decodes a plain (`P1`) or binary (`P4`) PBM image, with black pixels
+    /// as `true`.
+    pub fn from_pbm(data: &[u8]) -> Result<Self, FormatError> {
+        let mut header = Netpbm { data, pos: 0 };
+        let (raw, width, height) = header.header(b"P1", b"P4", "PBM")?;
+        let len = area(width, height)?;
+        let tiles = if raw {
+            let stride = width.div_ceil(8);
+            let pixels = header.raw(area(stride, height)?)?;
+            (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(|(x, y)| pixels[y * stride + x / 8] & (0x80 >> (x % 8)) != 0)
+                .collect()
+        } else {
+            // Plain PBM pixels don't need to be separated by whitespace.
+            let tiles: Vec<_> = (data[header.pos..].iter())
+                .filter(|b| !b.is_ascii_whitespace())
+                .map(|b| match b {
+                    b'0' => Ok(false),
+                    b'1' => Ok(true),
+                    _ => Err(FormatError::BadHeader("expected '0' or '1'")),
+                })
+                .take(len)
+                .collect::<Result<_, _>>()?;
+            if tiles.len() < len {
+                return Err(FormatError::Truncated);
+            }
+            tiles
+        };
+        Ok(from_parts(width, tiles))
+    }
+}
+
+/// Tiles that can be stored as the grey level of a PGM pixel.
+pub trait GrayLevel: Copy {
+    fn to_level(self) -> u64;
+    fn from_level(level: u64) -> Option<Self>;
+}
+
+macro_rules! gray_level {
+    ($($ty:ty),*) => {$(
+        impl GrayLevel for $ty {
+            fn to_level(self) -> u64 {
+                self as u64
+            }
+
+            fn from_level(level: u64) -> Option<Self> {
+                level.try_into().ok()
+            }
+        }
+    )*};
+}
+
+gray_level!(u8, u16, u32, u64, usize);
+
+impl<T: GrayLevel> Map<T> {
+    /// Encodes the map as a binary PGM image, scaled so that the largest
+    /// value in the map is white.
+    ///
+    /// ```
+    /// use aoc::map::{FormatError, Map};
+    ///
+    /// let mut map = Map::new(3, 2, 0u16);
+    /// map[(1, 0)] = 200;
+    /// let pgm = map.to_pgm().unwrap();
+    /// assert!(pgm.starts_with(b"P5\n3 2\n200\n"));
+    /// assert_eq!(Map::from_pgm(&pgm).unwrap(), map);
+    ///
+    /// // Levels above 255 take two bytes each.
+    /// map[(2, 1)] = 1000;
+    /// let pgm = map.to_pgm().unwrap();
+    /// assert_eq!(Map::from_pgm(&pgm).unwrap(), map);
+    /// let cut = Map::<u16>::from_pgm(&pgm[..pgm.len() - 1]);
+    /// assert!(matches!(cut, Err(FormatError::Truncated)));
+    ///
+    /// let huge = Map::<u8>::from_pgm(b"P5 99999999999 99999999999 255\n");
+    /// assert!(matches!(huge, Err(FormatError::BadHeader(_))));
+    /// let deep = Map::<u32>::from_pgm(b"P5 1 1 70000\n\0\0\0");
+    /// assert!(matches!(deep, Err(FormatError::BadHeader(_))));
+    /// ```
+    pub fn to_pgm(&self) -> Result<Vec<u8>, FormatError> {
+        let (width, height) = size(self);
+        let max = self
+            .tiles
+            .iter()
+            .map(|t| t.to_level())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        if max > u16::MAX as u64 {
+            return Err(FormatError::TooLarge(max));
+        }
+        let mut out = format!("P5\n{width} {height}\n{max}\n").into_bytes();
+        for tile in &self.tiles {
+            let level = tile.to_level() as u16;
+            if max < 256 {
+                out.push(level as u8);
+            } else {
+                out.extend(level.to_be_bytes());
+            }
+        }
+        Ok(out)
+    }
+
+    /// Decodes a plain (`P2`) or binary (`P5`) PGM image, with each tile
+    /// being the grey level of its pixel.
+    pub fn from_pgm(data: &[u8]) -> Result<Self, FormatError> {
+        let mut header = Netpbm { data, pos: 0 };
+        let (raw, width, height) = header.header(b"P2", b"P5", "PGM")?;
+        let max = header.number()?;
+        if !(1..=u16::MAX as u64).contains(&max) {
+            return Err(FormatError::BadHeader("maxval out of range"));
+        }
+        let len = area(width, height)?;
+        let levels: Vec<u64> = if raw {
+            if max < 256 {
+                header.raw(len)?.iter().map(|b| *b as u64).collect()
+            } else {
+                (header.raw(area(len, 2)?)?.chunks(2))
+                    .map(|b| u16::from_be_bytes([b[0], b[1]]) as u64)
+                    .collect()
+            }
+        } else {
+            (0..len)
+                .map(|_| header.number())
+                .collect::<Result<_, _>>()?
+        };
+        let tiles = (levels.into_iter())
+            .map(|level| T::from_level(level).ok_or(FormatError::BadLevel(level)))
+            .collect::<Result<_, _>>()?;
+        Ok(from_parts(width, tiles))
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::{from_parts, size, Map};
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize)]
+    #[serde(rename = "Map")]
+    struct MapRef<'a, T> {
+        width: usize,
+        tiles: &'a [T],
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename = "Map")]
+    struct MapOwned<T> {
+        width: usize,
+        tiles: Vec<T>,
+    }
+
+    /// Maps are stored as their width and a flat list of tiles, row by row.
+    impl<T: Serialize> Serialize for Map<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            MapRef {
+                width: size(self).0,
+                tiles: &self.tiles,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for Map<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let MapOwned { width, tiles } = MapOwned::deserialize(deserializer)?;
+            let fits = match width {
+                0 => tiles.is_empty(),
+                width => tiles.len() % width == 0,
+            };
+            if !fits {
+                return Err(de::Error::custom(format!(
+                    "{} tiles do not fill rows of width {width}",
+                    tiles.len()
+                )));
+            }
+            Ok(from_parts(width, tiles))
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! Number theory for puzzles about periods and remainders.
+//!
+//! ```
+//! use aoc::math;
+//!
+//! // Two robots loop every 6 and 10 steps; when are they at steps 3 and 5
+//! // of their loops at the same time?
+//! assert_eq!(math::crt(&[(3, 6), (5, 10)]), Some((15, 30)));
+//! assert_eq!(math::crt(&[(1, 6), (2, 10)]), None);
+//! assert_eq!(math::lcm(&[6, 10, 15]), 30);
+//! ```
+
+/// The greatest common divisor, which is never negative.
+pub fn gcd(mut a: i64, mut b: i64) -> i64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+/// The extended Euclidean algorithm: `(g, x, y)` where `g` is the gcd of
+/// `a` and `b` and `a * x + b * y == g`.
+pub fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// The least common multiple of all of `values`, which is 1 if there are
+/// none.
+///
+/// Panics if the lcm doesn't fit in an `i64`.
+pub fn lcm(values: &[i64]) -> i64 {
+    values.iter().fold(1, |acc, &v| {
+        if v == 0 {
+            0
+        } else {
+            (acc / gcd(acc, v))
+                .checked_mul(v)
+                .and_then(i64::checked_abs)
+                .expect("the lcm overflows an i64")
+        }
+    })
+}
+
+/// The `x` in `0..m` with `a * x ≡ 1 (mod m)`, if `a` and `m` are coprime.
+///
+/// Panics if `m` isn't positive.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    assert!(m > 0, "mod_inverse needs a positive modulus, not {m}");
+    let (g, x, _) = egcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// `base` to the power `exp`, modulo `m`.
+///
+/// Panics if `m` is zero.
+pub fn mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    assert!(m != 0, "mod_pow needs a nonzero modulus");
+    let m = m as u128;
+    let mut base = base as u128 % m;
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+/// Solves the system `x ≡ residue (mod modulus)` for every pair, giving
+/// the smallest non-negative solution and the lcm of the moduli, which it
+/// repeats with. The moduli needn't be coprime, but then the residues must
+/// agree where they overlap, or there is no solution.
+///
+/// Panics if a modulus isn't positive, or if the lcm of the moduli doesn't
+/// fit in an `i64`.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut x: i128 = 0;
+    let mut m: i64 = 1;
+    for &(residue, modulus) in congruences {
+        assert!(modulus > 0, "crt needs positive moduli, not {modulus}");
+        // Find k with x + m * k ≡ residue (mod modulus).
+        let (g, inv, _) = egcd(m, modulus);
+        let (residue, modulus, g) = (residue as i128, modulus as i128, g as i128);
+        let diff = residue - x;
+        if diff % g != 0 {
+            return None;
+        }
+        let step = modulus / g;
+        let k = (diff / g % step * inv as i128).rem_euclid(step);
+        x += m as i128 * k;
+        m = i64::try_from(m as i128 * step).expect("the lcm of the moduli overflows an i64");
+        x = x.rem_euclid(m as i128);
+    }
+    // x is below m, so it fits too.
+    Some((x as i64, m))
+}
@@ -0,0 +1,123 @@
+//! Caching for recursive solutions.
+//!
+//! A [`Memo`] runs a recursive closure, which is handed a function to make
+//! its recursive calls through, and remembers the result for each key:
+//!
+//! ```
+//! use aoc::memo::Memo;
+//!
+//! let mut memo = Memo::new();
+//! let fib = memo.recurse(90u64, |fib, n| if n < 2 { n } else { fib(n - 1) + fib(n - 2) });
+//! assert_eq!(fib, 2880067194370816120);
+//! ```
+//!
+//! When the argument carries more than the result depends on, such as a
+//! suffix of the input that only matters by its length, cache by a key
+//! projected from it with [`Memo::recurse_by_key`].
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Results of an expensive function, by key.
+#[derive(Clone, Debug)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    bound: Option<usize>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo {
+            cache: HashMap::new(),
+            bound: None,
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A cache holding at most `bound` results, which is emptied whenever
+    /// it fills up.
+    pub fn bounded(bound: usize) -> Self {
+        assert!(bound > 0, "a memo must be able to hold a result");
+        Memo {
+            cache: HashMap::new(),
+            bound: Some(bound),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// Stores `value` for `key`, making room first if the cache is full.
+    pub fn insert(&mut self, key: K, value: V) {
+        if self.bound == Some(self.cache.len()) && !self.cache.contains_key(&key) {
+            self.cache.clear();
+        }
+        self.cache.insert(key, value);
+    }
+
+    /// The result for `key`, computing it with `f` if it isn't cached.
+    pub fn get_or_insert_with(&mut self, key: K, f: impl FnOnce() -> V) -> &V
+    where
+        K: Clone,
+    {
+        if !self.cache.contains_key(&key) {
+            self.insert(key.clone(), f());
+        }
+        &self.cache[&key]
+    }
+
+    /// Computes `f(arg)`, where `f` makes its recursive calls through the
+    /// function it is given, so that each is only computed once.
+    pub fn recurse<F>(&mut self, arg: K, f: F) -> V
+    where
+        K: Clone,
+        V: Clone,
+        F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+    {
+        self.recurse_by_key(arg, K::clone, f)
+    }
+
+    /// Like [`recurse`](Self::recurse), but caches results by `key(&arg)`
+    /// rather than by the argument itself.
+    pub fn recurse_by_key<A, P, F>(&mut self, arg: A, key: P, f: F) -> V
+    where
+        V: Clone,
+        P: Fn(&A) -> K,
+        F: Fn(&mut dyn FnMut(A) -> V, A) -> V,
+    {
+        self.call(arg, &key, &f)
+    }
+
+    fn call<A, P, F>(&mut self, arg: A, key: &P, f: &F) -> V
+    where
+        V: Clone,
+        P: Fn(&A) -> K,
+        F: Fn(&mut dyn FnMut(A) -> V, A) -> V,
+    {
+        let k = key(&arg);
+        if let Some(value) = self.cache.get(&k) {
+            return value.clone();
+        }
+        let value = f(&mut |arg| self.call(arg, key, f), arg);
+        self.insert(k, value.clone());
+        value
+    }
+}
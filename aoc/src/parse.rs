@@ -0,0 +1,340 @@
+use crate::map::Map;
+
+use nom::branch::alt;
+use nom::bytes::complete::take_while1;
+use nom::character::complete::{line_ending, multispace0, space0};
+use nom::combinator::{recognize, value};
+use nom::error::{ContextError, ErrorKind, ParseError};
+use nom::multi::separated_list1;
+use nom::sequence::{pair, preceded, terminated};
+use nom::{IResult, OutputMode, Parser};
+use nom_language::error::VerboseError;
+
+use std::marker::PhantomData;
+
+pub mod compat;
+mod complete;
+mod report;
+
+pub use complete::{Complete, Incomplete, Unconsumed};
+pub use report::{run, PResult, ParseReport};
+
+/// Types that can be parsed from the start of a puzzle input.
+///
+/// Implemented for the primitive integer types, and derivable for structs
+/// with `#[derive(AocParse)]` and a `#[parse("...")]` template, where each
+/// `{field}` placeholder is filled using the field type's own `AocParse`
+/// implementation:
+///
+/// ```
+/// use aoc::prelude::*;
+///
+/// #[derive(AocParse)]
+/// #[parse("p={pos.0},{pos.1} v={vel.0},{vel.1}\n")]
+/// struct Robot {
+///     pos: (i32, i32),
+///     vel: (i32, i32),
+/// }
+///
+/// let robot = Robot::parse("p=0,4 v=3,-3\n").complete().unwrap();
+/// assert_eq!(robot.vel, (3, -3));
+/// ```
+///
+/// Tuple fields are filled element by element with `{field.N}`, and `Vec`
+/// fields take a separator with `{field:sep}`. A newline in the template
+/// matches either line ending. Every field must appear in the template,
+/// unless it is marked `#[parse(default)]` to start at its `Default` value.
+pub trait AocParse: Sized {
+    fn parse<'a, E>(i: &'a str) -> IResult<&'a str, Self, E>
+    where
+        E: ParseError<&'a str> + ContextError<&'a str>;
+}
+
+macro_rules! impl_aocparse {
+    ($($t:ident),*) => {
+        $(
+            impl AocParse for $t {
+                fn parse<'a, E>(i: &'a str) -> IResult<&'a str, Self, E>
+                where
+                    E: ParseError<&'a str> + ContextError<&'a str>,
+                {
+                    nom::character::complete::$t(i)
+                }
+            }
+        )*
+    };
+}
+
+impl_aocparse!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl AocParse for char {
+    fn parse<'a, E>(i: &'a str) -> IResult<&'a str, Self, E>
+    where
+        E: ParseError<&'a str> + ContextError<&'a str>,
+    {
+        nom::character::complete::anychar(i)
+    }
+}
+
+/// Whether `ch` can separate the numbers in a list for [`numbers`].
+fn is_separator(ch: char) -> bool {
+    matches!(ch, ' ' | '\t' | ',' | ';' | '|')
+}
+
+/// Parses a list of one or more numbers on a single line, separated by any
+/// mix of spaces, tabs, commas, semicolons and pipes, as in `7 6 4 2 1` or
+/// `75,47,61`.
+///
+/// Like the other parsers built from [`AocParse`] types here, it reports
+/// the [`VerboseError`] that [`run`] expects, so that only the number type
+/// needs naming, as in `numbers::<u32>()`. `Numbers::default()` gives the
+/// same parser with any other error type.
+///
+/// ```
+/// use aoc::parse::Numbers;
+/// use aoc::prelude::*;
+///
+/// assert_eq!(aoc::parse::run(numbers::<u32>(), "75,47,61").unwrap(), [75, 47, 61]);
+/// let (_, row) = Numbers::<u8, NomError<_>>::default().parse("7 6 4").unwrap();
+/// assert_eq!(row, [7, 6, 4]);
+/// ```
+pub fn numbers<'a, T: AocParse>() -> Numbers<T, VerboseError<&'a str>> {
+    Numbers::default()
+}
+
+/// The parser returned by [`numbers`].
+pub struct Numbers<T, E>(PhantomData<fn() -> (T, E)>);
+
+impl<T, E> Default for Numbers<T, E> {
+    fn default() -> Self {
+        Numbers(PhantomData)
+    }
+}
+
+impl<'a, T, E> Parser<&'a str> for Numbers<T, E>
+where
+    T: AocParse,
+    E: ParseError<&'a str> + ContextError<&'a str>,
+{
+    type Output = Vec<T>;
+    type Error = E;
+
+    fn process<OM: OutputMode>(&mut self, i: &'a str) -> nom::PResult<OM, &'a str, Vec<T>, E> {
+        separated_list1(take_while1(is_separator), T::parse).process::<OM>(i)
+    }
+}
+
+/// Parses one or more lines with `p`, which should not consume the line
+/// ending itself. Trailing spaces on each line, CRLF line endings and a
+/// missing final newline are all accepted.
+///
+/// The lines end at a blank line or the end of the input. Any other line
+/// that `p` rejects is an error, rather than where the lines stop, so that
+/// the error points into the line.
+pub fn lines<'a, O, E, P>(mut p: P) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+    P: Parser<&'a str, Output = O, Error = E>,
+    E: ParseError<&'a str>,
+{
+    move |i| {
+        let mut line = terminated(|i| p.parse(i), space0);
+        let (mut i, first) = line.parse(i)?;
+        let mut out = vec![first];
+        while let Ok((next, _)) = line_ending::<_, E>(i) {
+            if is_blank_line(next) {
+                return Ok((next, out));
+            }
+            let (rest, o) = line.parse(next)?;
+            out.push(o);
+            i = rest;
+        }
+        Ok((i, out))
+    }
+}
+
+/// Whether `i` starts with a line of nothing but spaces, or is the end of
+/// the input.
+fn is_blank_line(i: &str) -> bool {
+    let i = i.trim_start_matches([' ', '\t']);
+    i.is_empty() || i.starts_with(['\n', '\r'])
+}
+
+/// Matches the blank line between two blocks, along with the end of the
+/// previous block's last line if `p` left it.
+fn blank_line<'a, E: ParseError<&'a str>>(i: &'a str) -> IResult<&'a str, (), E> {
+    let blank = || preceded(space0, line_ending);
+    value((), alt((recognize(pair(line_ending, blank())), blank()))).parse(i)
+}
+
+/// Parses one or more blocks separated by blank lines with `p`, such as
+/// the machines of day 13. `p` may or may not consume the line ending at
+/// the end of each block, and any whitespace after the last block is
+/// skipped.
+pub fn blocks<'a, O, E, P>(mut p: P) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+    P: Parser<&'a str, Output = O, Error = E>,
+    E: ParseError<&'a str>,
+{
+    move |i| terminated(separated_list1(blank_line, |i| p.parse(i)), multispace0).parse(i)
+}
+
+/// Finds every integer in the rest of the input, including a leading `-`,
+/// and skips everything else, so that `p=0,4 v=3,-3` gives `[0, 4, 3, -3]`.
+pub fn signed_ints_anywhere<'a, T: AocParse>() -> SignedIntsAnywhere<T, VerboseError<&'a str>> {
+    SignedIntsAnywhere::default()
+}
+
+/// The parser returned by [`signed_ints_anywhere`].
+pub struct SignedIntsAnywhere<T, E>(PhantomData<fn() -> (T, E)>);
+
+impl<T, E> Default for SignedIntsAnywhere<T, E> {
+    fn default() -> Self {
+        SignedIntsAnywhere(PhantomData)
+    }
+}
+
+impl<'a, T, E> Parser<&'a str> for SignedIntsAnywhere<T, E>
+where
+    T: AocParse,
+    E: ParseError<&'a str> + ContextError<&'a str>,
+{
+    type Output = Vec<T>;
+    type Error = E;
+
+    fn process<OM: OutputMode>(&mut self, i: &'a str) -> nom::PResult<OM, &'a str, Vec<T>, E> {
+        Self::scan.process::<OM>(i)
+    }
+}
+
+impl<'a, T, E> SignedIntsAnywhere<T, E>
+where
+    T: AocParse,
+    E: ParseError<&'a str> + ContextError<&'a str>,
+{
+    fn scan(mut i: &'a str) -> IResult<&'a str, Vec<T>, E> {
+        let mut ints = Vec::new();
+        loop {
+            let start = i.find(|ch: char| ch.is_ascii_digit() || ch == '-');
+            let Some(start) = start else {
+                return Ok((&i[i.len()..], ints));
+            };
+            i = &i[start..];
+            // A `-` that isn't followed by a digit, or that the type can't
+            // take, is just punctuation.
+            let negative = i.starts_with('-');
+            if negative && !i[1..].starts_with(|ch: char| ch.is_ascii_digit()) {
+                i = &i[1..];
+                continue;
+            }
+            match T::parse(i) {
+                Ok((rest, int)) => {
+                    ints.push(int);
+                    i = rest;
+                }
+                Err(nom::Err::Error(_)) if negative => i = &i[1..],
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Parses lines of numbers, as for [`numbers`], into a `Map`. Every line
+/// must have the same count.
+pub fn number_grid<'a, T: AocParse + Clone>() -> NumberGrid<T, VerboseError<&'a str>> {
+    NumberGrid::default()
+}
+
+/// The parser returned by [`number_grid`].
+pub struct NumberGrid<T, E>(PhantomData<fn() -> (T, E)>);
+
+impl<T, E> Default for NumberGrid<T, E> {
+    fn default() -> Self {
+        NumberGrid(PhantomData)
+    }
+}
+
+impl<'a, T, E> Parser<&'a str> for NumberGrid<T, E>
+where
+    T: AocParse + Clone,
+    E: ParseError<&'a str> + ContextError<&'a str>,
+{
+    type Output = Map<T>;
+    type Error = E;
+
+    fn process<OM: OutputMode>(&mut self, i: &'a str) -> nom::PResult<OM, &'a str, Map<T>, E> {
+        Self::grid.process::<OM>(i)
+    }
+}
+
+impl<'a, T, E> NumberGrid<T, E>
+where
+    T: AocParse + Clone,
+    E: ParseError<&'a str> + ContextError<&'a str>,
+{
+    fn grid(i: &'a str) -> IResult<&'a str, Map<T>, E> {
+        let (rest, rows) = lines(Numbers::<T, E>::default())(i)?;
+        let width = rows[0].len();
+        if let Some(idx) = rows.iter().position(|row| row.len() != width) {
+            let line = i.lines().nth(idx).unwrap_or(i);
+            let err = E::from_error_kind(line, ErrorKind::Verify);
+            return Err(nom::Err::Error(E::add_context(
+                line,
+                "grid row, which must be as long as the first",
+                err,
+            )));
+        }
+        Ok((rest, Map::from_buf(width, rows.concat())))
+    }
+}
+
+/// Building blocks for the code generated by `#[derive(AocParse)]`.
+#[doc(hidden)]
+pub mod __derive {
+    use super::AocParse;
+    use nom::error::{ContextError, ParseError};
+    use nom::{IResult, Parser};
+
+    pub fn context<'a, O, E, F>(name: &'static str, i: &'a str, f: F) -> IResult<&'a str, O, E>
+    where
+        E: ContextError<&'a str>,
+        F: FnOnce(&'a str) -> IResult<&'a str, O, E>,
+    {
+        f(i).map_err(|e| e.map(|e| E::add_context(i, name, e)))
+    }
+
+    pub fn tag<'a, E>(tag: &'static str, i: &'a str) -> IResult<&'a str, &'a str, E>
+    where
+        E: ParseError<&'a str> + ContextError<&'a str>,
+    {
+        context(tag, i, nom::bytes::complete::tag(tag))
+    }
+
+    pub fn line_ending<'a, E>(i: &'a str) -> IResult<&'a str, &'a str, E>
+    where
+        E: ParseError<&'a str>,
+    {
+        nom::character::complete::line_ending(i)
+    }
+
+    pub fn field<'a, T, E>(name: &'static str, i: &'a str) -> IResult<&'a str, T, E>
+    where
+        T: AocParse,
+        E: ParseError<&'a str> + ContextError<&'a str>,
+    {
+        context(name, i, T::parse)
+    }
+
+    pub fn list<'a, T, E>(
+        name: &'static str,
+        sep: &'static str,
+        i: &'a str,
+    ) -> IResult<&'a str, Vec<T>, E>
+    where
+        T: AocParse,
+        E: ParseError<&'a str> + ContextError<&'a str>,
+    {
+        context(name, i, |i| {
+            nom::multi::separated_list1(nom::bytes::complete::tag(sep), T::parse).parse(i)
+        })
+    }
+}
@@ -0,0 +1,162 @@
+//! nom 7 style combinators on top of nom 8.
+//!
+//! nom 8 combinators return `impl Parser`, which has to be run with
+//! [`Parser::parse`]. These wrap them back into closures, so that parsers
+//! written as `pair(a, b)(i)` keep working. They are what the prelude
+//! exports; use `nom`'s own versions to build parsers the nom 8 way.
+
+use nom::error::{FromExternalError, ParseError};
+use nom::{IResult, Input, Offset, Parser};
+
+macro_rules! compat {
+    ($(
+        $(#[$attr:meta])*
+        fn $name:ident<$($gen:ident),*>($($arg:ident: $argty:ident),*) -> $out:ty
+        where { $($bounds:tt)* }
+        => $path:path;
+    )*) => {$(
+        $(#[$attr])*
+        pub fn $name<$($gen),*>($($arg: $argty),*) -> impl FnMut(I) -> IResult<I, $out, E>
+        where
+            $($bounds)*
+        {
+            let mut parser = $path($($arg),*);
+            move |i| parser.parse(i)
+        }
+    )*};
+}
+
+compat! {
+    /// See [`nom::branch::alt`].
+    fn alt<I, O, E, List>(list: List) -> O
+    where {
+        I: Clone,
+        E: ParseError<I>,
+        nom::branch::Choice<List>: Parser<I, Output = O, Error = E>,
+    } => nom::branch::alt;
+
+    /// See [`nom::combinator::map`].
+    fn map<I, O, E, F, G>(parser: F, f: G) -> O
+    where {
+        E: ParseError<I>,
+        F: Parser<I, Error = E>,
+        G: FnMut(F::Output) -> O,
+    } => nom::combinator::map;
+
+    /// See [`nom::combinator::map_res`].
+    fn map_res<I, O, E, E2, F, G>(parser: F, f: G) -> O
+    where {
+        I: Clone,
+        E: ParseError<I> + FromExternalError<I, E2>,
+        F: Parser<I, Error = E>,
+        G: FnMut(F::Output) -> Result<O, E2>,
+    } => nom::combinator::map_res;
+
+    /// See [`nom::combinator::opt`].
+    fn opt<I, O, E, F>(parser: F) -> Option<O>
+    where {
+        I: Clone,
+        E: ParseError<I>,
+        F: Parser<I, Output = O, Error = E>,
+    } => nom::combinator::opt;
+
+    /// See [`nom::combinator::recognize`].
+    fn recognize<I, E, F>(parser: F) -> I
+    where {
+        I: Clone + Offset + Input,
+        E: ParseError<I>,
+        F: Parser<I, Error = E>,
+    } => nom::combinator::recognize;
+
+    /// See [`nom::combinator::value`].
+    fn value<I, O, E, F>(val: O, parser: F) -> O
+    where {
+        O: Clone,
+        E: ParseError<I>,
+        F: Parser<I, Error = E>,
+    } => nom::combinator::value;
+
+    /// See [`nom::multi::many0`].
+    fn many0<I, O, E, F>(parser: F) -> Vec<O>
+    where {
+        I: Clone + Input,
+        E: ParseError<I>,
+        F: Parser<I, Output = O, Error = E>,
+    } => nom::multi::many0;
+
+    /// See [`nom::multi::many1`].
+    fn many1<I, O, E, F>(parser: F) -> Vec<O>
+    where {
+        I: Clone + Input,
+        E: ParseError<I>,
+        F: Parser<I, Output = O, Error = E>,
+    } => nom::multi::many1;
+
+    /// See [`nom::multi::separated_list0`].
+    fn separated_list0<I, O, E, F, G>(sep: G, parser: F) -> Vec<O>
+    where {
+        I: Clone + Input,
+        E: ParseError<I>,
+        F: Parser<I, Output = O, Error = E>,
+        G: Parser<I, Error = E>,
+    } => nom::multi::separated_list0;
+
+    /// See [`nom::multi::separated_list1`].
+    fn separated_list1<I, O, E, F, G>(sep: G, parser: F) -> Vec<O>
+    where {
+        I: Clone + Input,
+        E: ParseError<I>,
+        F: Parser<I, Output = O, Error = E>,
+        G: Parser<I, Error = E>,
+    } => nom::multi::separated_list1;
+
+    /// See [`nom::sequence::delimited`].
+    fn delimited<I, O, E, F, G, H>(first: F, second: G, third: H) -> O
+    where {
+        E: ParseError<I>,
+        F: Parser<I, Error = E>,
+        G: Parser<I, Output = O, Error = E>,
+        H: Parser<I, Error = E>,
+    } => nom::sequence::delimited;
+
+    /// See [`nom::sequence::pair`].
+    fn pair<I, O1, O2, E, F, G>(first: F, second: G) -> (O1, O2)
+    where {
+        E: ParseError<I>,
+        F: Parser<I, Output = O1, Error = E>,
+        G: Parser<I, Output = O2, Error = E>,
+    } => nom::sequence::pair;
+
+    /// See [`nom::sequence::preceded`].
+    fn preceded<I, O, E, F, G>(first: F, second: G) -> O
+    where {
+        E: ParseError<I>,
+        F: Parser<I, Error = E>,
+        G: Parser<I, Output = O, Error = E>,
+    } => nom::sequence::preceded;
+
+    /// See [`nom::sequence::separated_pair`].
+    fn separated_pair<I, O1, O2, E, F, G, H>(first: F, sep: G, second: H) -> (O1, O2)
+    where {
+        E: ParseError<I>,
+        F: Parser<I, Output = O1, Error = E>,
+        G: Parser<I, Error = E>,
+        H: Parser<I, Output = O2, Error = E>,
+    } => nom::sequence::separated_pair;
+
+    /// See [`nom::sequence::terminated`].
+    fn terminated<I, O, E, F, G>(first: F, second: G) -> O
+    where {
+        E: ParseError<I>,
+        F: Parser<I, Output = O, Error = E>,
+        G: Parser<I, Error = E>,
+    } => nom::sequence::terminated;
+
+    /// Applies a tuple of parsers in turn, which nom 8 does for the tuple
+    /// itself.
+    fn tuple<I, O, E, List>(list: List) -> O
+    where {
+        E: ParseError<I>,
+        List: Parser<I, Output = O, Error = E>,
+    } => std::convert::identity;
+}
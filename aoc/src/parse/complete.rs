@@ -0,0 +1,70 @@
+//! Checks that a parser used up all of its input.
+
+use nom::IResult;
+use nom_language::error::VerboseError;
+
+use std::fmt;
+
+/// Input left over after a parser succeeded, other than trailing
+/// whitespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unconsumed<'a> {
+    pub tail: &'a str,
+}
+
+impl fmt::Display for Unconsumed<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let line = self.tail.lines().next().unwrap_or_default();
+        let mut start: String = line.chars().take(40).collect();
+        if start.len() < line.len() {
+            start += "...";
+        }
+        write!(
+            f,
+            "{} bytes of input were not parsed, starting with {start:?}",
+            self.tail.len()
+        )
+    }
+}
+
+impl std::error::Error for Unconsumed<'_> {}
+
+/// Why [`Complete::complete`] failed.
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum Incomplete<'a> {
+    #[error("{0}")]
+    Failed(nom::Err<VerboseError<&'a str>>),
+    #[error(transparent)]
+    Unconsumed(Unconsumed<'a>),
+}
+
+/// Checks the result of a parser for leftover input.
+///
+/// This is implemented for results using [`VerboseError`], so that calling
+/// it also picks the error type of a generic parser such as `Map::parse`:
+///
+/// ```
+/// use aoc::parse::{Complete, Incomplete};
+/// use aoc::prelude::*;
+///
+/// let map = Map::<char>::parse("ab\ncd\n").complete().unwrap();
+/// assert_eq!(map.width(), 2);
+///
+/// let err = nom_u32("12 apples").complete().unwrap_err();
+/// assert!(matches!(err, Incomplete::Unconsumed(rest) if rest.tail == " apples"));
+/// ```
+pub trait Complete<'a, O> {
+    /// The parser's output, if it succeeded and left nothing but
+    /// whitespace.
+    fn complete(self) -> Result<O, Incomplete<'a>>;
+}
+
+impl<'a, O> Complete<'a, O> for IResult<&'a str, O, VerboseError<&'a str>> {
+    fn complete(self) -> Result<O, Incomplete<'a>> {
+        match self {
+            Ok((rest, output)) if rest.trim().is_empty() => Ok(output),
+            Ok((rest, _)) => Err(Incomplete::Unconsumed(Unconsumed { tail: rest })),
+            Err(err) => Err(Incomplete::Failed(err)),
+        }
+    }
+}
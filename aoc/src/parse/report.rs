@@ -0,0 +1,163 @@
+//! Turns nom errors into reports that point at the offending input.
+
+use nom::error::ErrorKind;
+use nom::{IResult, Offset, Parser};
+use nom_language::error::{VerboseError, VerboseErrorKind};
+
+use super::{Complete, Incomplete};
+
+use std::fmt;
+
+/// A parser result using [`VerboseError`], which keeps the context needed
+/// for a useful [`ParseReport`].
+pub type PResult<'a, O> = IResult<&'a str, O, VerboseError<&'a str>>;
+
+/// A parse failure, located in the input it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseReport {
+    /// The line number, from 1.
+    pub line: usize,
+    /// The column in characters, from 1.
+    pub column: usize,
+    /// The text of the offending line, without its line ending.
+    pub text: String,
+    pub message: String,
+    /// The contexts that were being parsed, innermost first.
+    pub context: Vec<&'static str>,
+}
+
+impl ParseReport {
+    /// Describes a problem at the start of `at`, which must be a slice of
+    /// `input`.
+    pub fn new(input: &str, at: &str, message: impl Into<String>) -> Self {
+        let offset = input.offset(at);
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |n| n + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |n| offset + n);
+        ParseReport {
+            line: before.matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            text: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            message: message.into(),
+            context: Vec::new(),
+        }
+    }
+
+    /// Reports a `VerboseError` from parsing `input`.
+    pub fn from_verbose(input: &str, err: &VerboseError<&str>) -> Self {
+        let Some((at, kind)) = err.errors.first() else {
+            return ParseReport::new(input, input, "invalid input");
+        };
+        let mut errors = &err.errors[1..];
+        let message = match (kind, errors.first()) {
+            // Something was parsed but then rejected, so name what it was.
+            (
+                VerboseErrorKind::Nom(ErrorKind::Verify),
+                Some((ctx_at, VerboseErrorKind::Context(name))),
+            ) if ctx_at == at => {
+                errors = &errors[1..];
+                format!("invalid {name}")
+            }
+            (VerboseErrorKind::Char(ch), _) => format!("expected {ch:?}"),
+            (VerboseErrorKind::Context(name), _) => format!("invalid {name}"),
+            // The derive names the literal text it was looking for.
+            (
+                VerboseErrorKind::Nom(ErrorKind::Tag),
+                Some((ctx_at, VerboseErrorKind::Context(tag))),
+            ) if ctx_at == at => {
+                errors = &errors[1..];
+                format!("expected {tag:?}")
+            }
+            (VerboseErrorKind::Nom(kind), _) => format!("expected {}", describe(kind)),
+        };
+        let mut report = ParseReport::new(input, at, message);
+        report.context = (errors.iter())
+            .filter_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(name) => Some(*name),
+                _ => None,
+            })
+            .collect();
+        report
+    }
+}
+
+/// What a failed nom parser was looking for.
+fn describe(kind: &ErrorKind) -> &str {
+    match kind {
+        ErrorKind::Digit => "a number",
+        ErrorKind::Alpha => "a letter",
+        ErrorKind::AlphaNumeric => "a letter or digit",
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace",
+        ErrorKind::CrLf => "a line ending",
+        ErrorKind::Eof => "the end of the input",
+        ErrorKind::Tag => "specific text",
+        ErrorKind::Many1 | ErrorKind::SeparatedList | ErrorKind::TakeWhile1 => "at least one item",
+        ErrorKind::Verify | ErrorKind::MapRes | ErrorKind::MapOpt => "a valid value",
+        kind => kind.description(),
+    }
+}
+
+impl fmt::Display for ParseReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let number = self.line.to_string();
+        let pad = " ".repeat(number.len());
+        writeln!(
+            f,
+            "parse error at line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "{pad} |")?;
+        writeln!(f, "{number} | {}", self.text)?;
+        // Copy the line's tabs so the caret lines up however they render.
+        let indent: String = (self.text.chars().chain(std::iter::repeat(' ')))
+            .take(self.column - 1)
+            .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+            .collect();
+        write!(f, "{pad} | {indent}^")?;
+        if !self.context.is_empty() {
+            write!(f, "\n{pad} = while parsing {}", self.context.join(" in "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseReport {}
+
+/// Runs `parser` over the whole of `input`, reporting where it failed if it
+/// did, or where it stopped if anything but whitespace is left over.
+///
+/// ```
+/// use aoc::prelude::*;
+///
+/// let err = aoc::parse::run(lines(numbers::<u32>()), "1 2\n3 x\n").unwrap_err();
+/// assert!(err.to_string().starts_with("parse error at line 2, column 3"));
+///
+/// let pair = separated_pair(nom_u32, space1, nom_u32);
+/// let err = aoc::parse::run(lines(pair), "1 2\n3 x\n").unwrap_err();
+/// assert!(err.to_string().starts_with("parse error at line 2, column 3: expected a number"));
+///
+/// // The caret keeps the line's tabs, to stay under the right character.
+/// let err = aoc::parse::run(lines(numbers::<u32>()), "1\t2\tx\n").unwrap_err();
+/// assert!(err.to_string().ends_with("\n  |  \t \t^"));
+/// ```
+pub fn run<'a, O, P>(mut parser: P, input: &'a str) -> anyhow::Result<O>
+where
+    P: Parser<&'a str, Output = O, Error = VerboseError<&'a str>>,
+{
+    match parser.parse(input).complete() {
+        Ok(output) => Ok(output),
+        Err(Incomplete::Unconsumed(rest)) => {
+            Err(ParseReport::new(input, rest.tail, "unexpected input").into())
+        }
+        Err(Incomplete::Failed(nom::Err::Error(err) | nom::Err::Failure(err))) => {
+            Err(ParseReport::from_verbose(input, &err).into())
+        }
+        Err(Incomplete::Failed(nom::Err::Incomplete(_))) => {
+            anyhow::bail!("input ended unexpectedly")
+        }
+    }
+}
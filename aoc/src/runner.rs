@@ -0,0 +1,443 @@
+//! The driver behind `#[aoc::main]`: argument handling, input loading,
+//! answer reporting and error reporting.
+
+use anyhow::{bail, Context, Result};
+
+use crate::inputs::{self, Inputs, Slot};
+use crate::{bench, viz};
+
+use std::ffi::OsString;
+use std::fmt::{self, Display, Write};
+use std::io::{stdin, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "usage: [-v] [--play] [--viz FILE] [--gzip] [--json] [--example N] [INPUT]
+       [--bench N [--baseline NAME] [--save-baseline NAME]]
+
+Reads the puzzle input from INPUT, or from stdin if INPUT is `-`. Without
+INPUT, the input is read from the directory named by AOC_INPUT_DIR, or
+`inputs` if it is unset, as dayNN.txt, falling back to stdin if there is
+no such file and stdin is redirected. With --example N the input is
+examples/dayNN-N.txt in the day's crate instead. Inputs ending in `.gz`,
+or any input with --gzip, are decompressed first.
+
+With --json the answers and timings are printed as a single JSON object
+instead of `Part N: ...` lines.
+
+With -v solvers print debugging output, such as map dumps, to stderr.
+--play plays recorded animations back on stderr if it is a terminal, and
+--viz writes only the map dumps and recordings to FILE instead.
+
+With --bench the solver is run N times and the min, median and p95 time
+of each phase is printed, or with --json printed as a JSON object in
+nanoseconds. The results can be saved as a named baseline under
+aoc-bench in $CARGO_TARGET_DIR, or else the workspace's target
+directory, and compared against one with --baseline.";
+
+fn flag_value<I: Iterator<Item = OsString>>(args: &mut I, flag: &str) -> Result<String> {
+    match args.next().map(OsString::into_string) {
+        Some(Ok(value)) => Ok(value),
+        Some(Err(value)) => bail!("invalid value {value:?} for {flag}"),
+        None => bail!("{flag} needs a value\n\n{USAGE}"),
+    }
+}
+
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+/// Whether the runner was asked for debugging output with `-v`.
+pub fn verbose() -> bool {
+    VERBOSE.load(Ordering::Relaxed)
+}
+
+/// Sets whether `vprintln!` prints, and if so sends [`viz`] dumps to
+/// stderr as well.
+///
+/// [`viz`]: crate::viz
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+    if verbose {
+        viz::to_stderr();
+    }
+}
+
+/// Prints to stderr like `eprintln!`, but only when running with `-v`.
+#[macro_export]
+macro_rules! vprintln {
+    ($($arg:tt)*) => {
+        if $crate::verbose() {
+            ::std::eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Command line options shared by every day.
+#[derive(Clone, Debug, Default)]
+pub struct Args {
+    pub input: Option<PathBuf>,
+    pub example: Option<u32>,
+    pub gzip: bool,
+    pub json: bool,
+    pub verbose: bool,
+    pub play: bool,
+    pub viz: Option<PathBuf>,
+    pub bench: Option<usize>,
+    pub baseline: Option<String>,
+    pub save_baseline: Option<String>,
+    pub help: bool,
+}
+
+impl Args {
+    pub fn parse<I: IntoIterator<Item = OsString>>(args: I) -> Result<Self> {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.to_str() {
+                Some("--gzip" | "-z") => parsed.gzip = true,
+                Some("--json") => parsed.json = true,
+                Some("--verbose" | "-v") => parsed.verbose = true,
+                Some("--play") => parsed.play = true,
+                Some("--viz") => parsed.viz = Some(flag_value(&mut args, "--viz")?.into()),
+                Some("--example" | "-e") => {
+                    let n = flag_value(&mut args, "--example")?;
+                    let n = n
+                        .parse()
+                        .with_context(|| format!("invalid example number `{n}`"))?;
+                    parsed.example = Some(n);
+                }
+                Some("--bench") => {
+                    let n = flag_value(&mut args, "--bench")?;
+                    let n = n
+                        .parse()
+                        .with_context(|| format!("invalid --bench count `{n}`"))?;
+                    parsed.bench = Some(n);
+                }
+                Some("--baseline") => parsed.baseline = Some(flag_value(&mut args, "--baseline")?),
+                Some("--save-baseline") => {
+                    parsed.save_baseline = Some(flag_value(&mut args, "--save-baseline")?)
+                }
+                Some("--help" | "-h") => parsed.help = true,
+                Some(flag) if flag.starts_with('-') && flag != "-" => {
+                    bail!("unknown option `{flag}`\n\n{USAGE}")
+                }
+                _ if parsed.input.is_some() => bail!("unexpected argument {arg:?}\n\n{USAGE}"),
+                Some("-") => parsed.input = Some(PathBuf::from("-")),
+                _ => parsed.input = Some(arg.into()),
+            }
+        }
+        if parsed.example.is_some() && parsed.input.is_some() {
+            bail!("--example cannot be used with an INPUT file\n\n{USAGE}");
+        }
+        Ok(parsed)
+    }
+
+    /// Which cached input to use if no INPUT was given.
+    pub fn slot(&self) -> Slot {
+        self.example.map_or(Slot::Real, Slot::Example)
+    }
+}
+
+fn decompress(raw: Vec<u8>) -> Result<Vec<u8>> {
+    #[cfg(feature = "gzip")]
+    {
+        let mut buf = Vec::new();
+        flate2::read::MultiGzDecoder::new(raw.as_slice()).read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+    #[cfg(not(feature = "gzip"))]
+    {
+        let _ = raw;
+        bail!("gzip support is disabled, enable the `gzip` feature of the aoc crate")
+    }
+}
+
+/// Reads the whole puzzle input for `day` described by `args`.
+///
+/// Without an INPUT, the day's file in the input directory is used if it
+/// exists. Otherwise redirected stdin is read, so that `dayN < input.txt`
+/// still works, and if stdin is a terminal the error says where the input
+/// was expected rather than waiting for it to be typed in.
+pub fn read_input(args: &Args, day: &Day) -> Result<&'static str> {
+    match args.input.as_deref() {
+        Some(path) if path == Path::new("-") => load_input(None, args.gzip),
+        Some(path) => load_input(Some(path), args.gzip),
+        None => match args.slot() {
+            Slot::Real => match Inputs::from_env().resolve(day.day) {
+                Ok(path) => load_input(Some(&path), args.gzip),
+                Err(_) if !stdin().is_terminal() => load_input(None, args.gzip),
+                Err(missing) => Err(missing.into()),
+            },
+            Slot::Example(n) => {
+                let path = inputs::resolve_example(Path::new(day.dir), day.day, n)?;
+                load_input(Some(&path), args.gzip)
+            }
+        },
+    }
+}
+
+/// Reads the whole puzzle input from `path`, or from stdin if it is `None`.
+///
+/// Inputs ending in `.gz` are decompressed, as is any input if `gzip` is
+/// set. The input is leaked so that solvers can borrow from it for the rest
+/// of the program.
+pub fn load_input(path: Option<&Path>, gzip: bool) -> Result<&'static str> {
+    let (raw, name) = match path {
+        Some(path) => (
+            std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?,
+            path.display().to_string(),
+        ),
+        None => {
+            let mut raw = Vec::new();
+            stdin()
+                .read_to_end(&mut raw)
+                .context("failed to read stdin")?;
+            (raw, "stdin".to_string())
+        }
+    };
+
+    let gzip = gzip || path.is_some_and(|p| p.extension() == Some("gz".as_ref()));
+    let raw = if gzip {
+        decompress(raw).with_context(|| format!("failed to decompress {name}"))?
+    } else {
+        raw
+    };
+
+    let input = String::from_utf8(raw).with_context(|| format!("{name} is not valid UTF-8"))?;
+    Ok(Box::leak(input.into_boxed_str()))
+}
+
+/// Formats a duration with a unit suited to its size.
+pub fn format_duration(time: Duration) -> String {
+    if time < Duration::from_millis(1) {
+        format!("{}µs", time.as_micros())
+    } else if time < Duration::from_secs(1) {
+        format!("{:.2}ms", time.as_secs_f64() * 1e3)
+    } else {
+        format!("{:.2}s", time.as_secs_f64())
+    }
+}
+
+/// The answers to both parts of a puzzle.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Solution<A, B> {
+    pub part1: A,
+    pub part2: B,
+}
+
+/// A solver's answers as text, along with how long each phase took.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: String,
+    pub part2: String,
+    pub timings: Vec<(&'static str, Duration)>,
+}
+
+impl<A: Display, B: Display> From<Solution<A, B>> for Answers {
+    fn from(solution: Solution<A, B>) -> Self {
+        Answers {
+            part1: solution.part1.to_string(),
+            part2: solution.part2.to_string(),
+            timings: Vec::new(),
+        }
+    }
+}
+
+pub(crate) fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            ch if ch.is_control() => write!(out, "\\u{:04x}", ch as u32).unwrap(),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
+
+impl Answers {
+    /// The total time taken across all phases.
+    pub fn elapsed(&self) -> Duration {
+        self.timings.iter().map(|(_, time)| *time).sum()
+    }
+
+    /// Renders the answers as a JSON object, with timings in microseconds.
+    pub fn to_json(&self) -> String {
+        let timings: Vec<_> = (self.timings.iter())
+            .map(|(phase, time)| format!("{}:{}", json_string(phase), time.as_micros()))
+            .collect();
+        format!(
+            "{{\"part1\":{},\"part2\":{},\"timings_us\":{{{}}}}}",
+            json_string(&self.part1),
+            json_string(&self.part2),
+            timings.join(",")
+        )
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Part 1: {}", self.part1)?;
+        write!(f, "Part 2: {}", self.part2)
+    }
+}
+
+/// The result of a solver, converted into a uniform `Result`.
+///
+/// Solvers that print their own output return `()`, and report no answers.
+pub trait Report {
+    fn report(self) -> Result<Option<Answers>>;
+}
+
+impl Report for () {
+    fn report(self) -> Result<Option<Answers>> {
+        Ok(None)
+    }
+}
+
+impl<A: Display, B: Display> Report for Solution<A, B> {
+    fn report(self) -> Result<Option<Answers>> {
+        Ok(Some(self.into()))
+    }
+}
+
+impl<T: Report, E: Into<anyhow::Error>> Report for std::result::Result<T, E> {
+    fn report(self) -> Result<Option<Answers>> {
+        self.map_err(Into::into)?.report()
+    }
+}
+
+/// Generates a `#[test]` that runs a solver function or [`Solver`] on a
+/// puzzle example and checks its answers.
+///
+/// The example is either given inline with `input = "..."`, or read from a
+/// file relative to the day's crate with `file = "examples/dayNN-1.txt"`.
+/// Only the parts listed are checked, since some puzzles have a separate
+/// example for each part, and a `Solver` only runs those parts.
+///
+/// [`Solver`]: crate::Solver
+///
+/// ```
+/// use aoc::prelude::*;
+///
+/// fn day1(inp: &str) -> Solution<usize, usize> {
+///     Solution { part1: inp.lines().count(), part2: inp.len() }
+/// }
+///
+/// example_test!(day1, example, input = "3   4\n4   3\n", part1 = 2, part2 = 12);
+/// ```
+#[macro_export]
+macro_rules! example_test {
+    ($solver:ident, $name:ident, file = $path:literal, $($part:ident = $expected:expr),+ $(,)?) => {
+        $crate::example_test!(
+            $solver,
+            $name,
+            input = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $path)),
+            $($part = $expected),+
+        );
+    };
+    ($solver:ident, $name:ident, input = $input:expr, $($part:ident = $expected:expr),+ $(,)?) => {
+        #[cfg(test)]
+        #[test]
+        fn $name() {
+            let parts = [$(stringify!($part)),+];
+            let answers = $crate::solver::Example::run_example($solver, $input, &parts)
+                .expect("solver failed");
+            $(
+                assert_eq!(
+                    answers.$part,
+                    ::std::string::ToString::to_string(&$expected),
+                    "wrong {}",
+                    stringify!($part),
+                );
+            )+
+        }
+    };
+}
+
+/// A registered solver, generated by `#[aoc::main]` as `pub static DAY`.
+#[derive(Clone, Copy, Debug)]
+pub struct Day {
+    pub day: u32,
+    /// The directory of the day's crate, which holds its examples.
+    pub dir: &'static str,
+    pub solve: fn(&'static str) -> Result<Option<Answers>>,
+}
+
+impl Day {
+    /// Runs the solver on `input`, recording how long it took.
+    pub fn run(&self, input: &'static str) -> Result<Option<Answers>> {
+        let start = Instant::now();
+        let res = (self.solve)(input);
+        let elapsed = start.elapsed();
+
+        let mut answers = res?;
+        if let Some(answers) = answers.as_mut().filter(|a| a.timings.is_empty()) {
+            answers.timings.push(("solve", elapsed));
+        }
+        Ok(answers)
+    }
+}
+
+/// Prints `err` and the chain of errors that caused it to stderr.
+pub fn print_error(err: &anyhow::Error) {
+    eprintln!("error: {err}");
+    for cause in err.chain().skip(1) {
+        eprintln!("  caused by: {cause}");
+    }
+}
+
+/// Runs a single day on the input selected by the command line arguments.
+pub fn main(day: &Day) -> ExitCode {
+    let res = Args::parse(std::env::args_os().skip(1)).and_then(|args| {
+        if args.help {
+            println!("{USAGE}");
+            return Ok(());
+        }
+        set_verbose(args.verbose);
+        if args.play {
+            viz::play_to_stderr();
+        }
+        if let Some(path) = &args.viz {
+            viz::to_file(path)?;
+        }
+        let input = read_input(&args, day)?;
+
+        if let Some(iterations) = args.bench {
+            let baseline = (args.baseline.as_deref())
+                .map(|name| bench::Report::load(name, day))
+                .transpose()?;
+            let report = bench::bench(day, input, iterations)?;
+            if args.json {
+                println!("{}", report.to_json(baseline.as_ref()));
+            } else {
+                report.print(baseline.as_ref());
+            }
+            if let Some(name) = &args.save_baseline {
+                report.save(name, day)?;
+            }
+            return Ok(());
+        }
+
+        if let Some(answers) = day.run(input)? {
+            if args.json {
+                println!("{}", answers.to_json());
+            } else {
+                println!("{answers}");
+            }
+        }
+        Ok(())
+    });
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            print_error(&err);
+            ExitCode::FAILURE
+        }
+    }
+}
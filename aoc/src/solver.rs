@@ -0,0 +1,110 @@
+//! Solvers split into separate parse, part 1 and part 2 phases.
+
+use anyhow::Result;
+
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use crate::runner::{Answers, Report};
+
+/// A puzzle solution split into phases, so that each can be timed and
+/// tested on its own.
+///
+/// Implement it on a unit struct named after the day and register it with
+/// `#[aoc::main]`:
+///
+/// ```
+/// use aoc::prelude::*;
+///
+/// struct Day1;
+///
+/// #[main]
+/// impl Solver for Day1 {
+///     type Input = Vec<u32>;
+///
+///     fn parse(inp: &'static str) -> Result<Vec<u32>> {
+///         Ok(inp.lines().map(str::parse).collect::<Result<_, _>>()?)
+///     }
+///
+///     fn part1(input: &Vec<u32>) -> impl fmt::Display {
+///         input.iter().sum::<u32>()
+///     }
+///
+///     fn part2(input: &Vec<u32>) -> impl fmt::Display {
+///         input.iter().max().copied().unwrap_or_default()
+///     }
+/// }
+/// ```
+pub trait Solver {
+    /// The parsed puzzle input, which may borrow from the input text.
+    type Input;
+
+    fn parse(inp: &'static str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> impl Display;
+    fn part2(input: &Self::Input) -> impl Display;
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let res = f();
+    (res, start.elapsed())
+}
+
+/// Runs each phase of `S` in turn, timing them separately.
+pub fn solve<S: Solver>(inp: &'static str) -> Result<Option<Answers>> {
+    let (input, parse) = timed(|| S::parse(inp));
+    let input = input?;
+    let (part1, part1_time) = timed(|| S::part1(&input).to_string());
+    let (part2, part2_time) = timed(|| S::part2(&input).to_string());
+    Ok(Some(Answers {
+        part1,
+        part2,
+        timings: vec![
+            ("parse", parse),
+            ("part1", part1_time),
+            ("part2", part2_time),
+        ],
+    }))
+}
+
+#[doc(hidden)]
+pub struct FnMarker;
+#[doc(hidden)]
+pub struct SolverMarker;
+
+/// Either a solver function or a [`Solver`], run by `example_test!`.
+///
+/// The marker type lets both be accepted without the implementations
+/// overlapping.
+#[doc(hidden)]
+pub trait Example<Marker> {
+    /// Runs the solver on `inp`, skipping any parts not in `parts` if the
+    /// solver allows it. Skipped parts are left empty.
+    fn run_example(self, inp: &'static str, parts: &[&str]) -> Result<Answers>;
+}
+
+impl<F, R> Example<FnMarker> for F
+where
+    F: FnOnce(&'static str) -> R,
+    R: Report,
+{
+    fn run_example(self, inp: &'static str, _parts: &[&str]) -> Result<Answers> {
+        self(inp)
+            .report()?
+            .ok_or_else(|| anyhow::anyhow!("solver returned no answers"))
+    }
+}
+
+impl<S: Solver> Example<SolverMarker> for S {
+    fn run_example(self, inp: &'static str, parts: &[&str]) -> Result<Answers> {
+        let input = S::parse(inp)?;
+        let mut answers = Answers::default();
+        if parts.contains(&"part1") {
+            answers.part1 = S::part1(&input).to_string();
+        }
+        if parts.contains(&"part2") {
+            answers.part2 = S::part2(&input).to_string();
+        }
+        Ok(answers)
+    }
+}
@@ -0,0 +1,141 @@
+//! Colours and text styles for rendering tiles in a terminal.
+
+use std::fmt;
+
+/// A 24-bit colour.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// The colour used for a tile character when there is no colour mapping:
+    /// black for empty space, grey for walls and digits, and an arbitrary
+    /// but stable colour for anything else.
+    pub fn for_char(ch: char) -> Self {
+        match ch {
+            '.' | ' ' => Rgb(0, 0, 0),
+            '#' => Rgb(160, 160, 160),
+            '0'..='9' => {
+                let v = 40 + (ch as u8 - b'0') * 23;
+                Rgb(v, v, v)
+            }
+            ch => {
+                let h = (ch as u32).wrapping_mul(2654435761);
+                Rgb(
+                    (h >> 24) as u8 | 0x40,
+                    (h >> 16) as u8 | 0x40,
+                    (h >> 8) as u8 | 0x40,
+                )
+            }
+        }
+    }
+}
+
+/// A terminal colour: one of the basic ANSI colours, or any 24-bit colour.
+///
+/// The basic colours can be named in `#[tile(..., fg = "red")]`, in lower
+/// case, and any colour given as `"#rrggbb"`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Grey,
+    Rgb(Rgb),
+}
+
+impl Color {
+    /// An approximation of how the colour looks in a typical terminal, for
+    /// drawing images.
+    pub fn rgb(self) -> Rgb {
+        match self {
+            Color::Black => Rgb(0, 0, 0),
+            Color::Red => Rgb(205, 49, 49),
+            Color::Green => Rgb(13, 188, 121),
+            Color::Yellow => Rgb(229, 229, 16),
+            Color::Blue => Rgb(36, 114, 200),
+            Color::Magenta => Rgb(188, 63, 188),
+            Color::Cyan => Rgb(17, 168, 205),
+            Color::White => Rgb(229, 229, 229),
+            Color::Grey => Rgb(118, 118, 118),
+            Color::Rgb(rgb) => rgb,
+        }
+    }
+
+    /// Writes the SGR parameters selecting this colour, for the foreground
+    /// or background.
+    fn write_sgr(self, f: &mut impl fmt::Write, background: bool) -> fmt::Result {
+        let base = if background { 40 } else { 30 };
+        match self {
+            Color::Black => write!(f, "{}", base),
+            Color::Red => write!(f, "{}", base + 1),
+            Color::Green => write!(f, "{}", base + 2),
+            Color::Yellow => write!(f, "{}", base + 3),
+            Color::Blue => write!(f, "{}", base + 4),
+            Color::Magenta => write!(f, "{}", base + 5),
+            Color::Cyan => write!(f, "{}", base + 6),
+            Color::White => write!(f, "{}", base + 7),
+            Color::Grey => write!(f, "{}", base + 60),
+            Color::Rgb(Rgb(r, g, b)) => write!(f, "{};2;{r};{g};{b}", base + 8),
+        }
+    }
+}
+
+/// How a tile is drawn in a terminal, on top of its character.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub bold: bool,
+}
+
+impl Style {
+    pub const PLAIN: Style = Style {
+        fg: None,
+        bg: None,
+        bold: false,
+    };
+
+    pub const fn fg(self, color: Color) -> Self {
+        Style {
+            fg: Some(color),
+            ..self
+        }
+    }
+
+    pub const fn bg(self, color: Color) -> Self {
+        Style {
+            bg: Some(color),
+            ..self
+        }
+    }
+
+    pub const fn bold(self) -> Self {
+        Style { bold: true, ..self }
+    }
+
+    pub fn is_plain(&self) -> bool {
+        *self == Style::PLAIN
+    }
+
+    /// Writes the escape sequence switching to this style from any other.
+    pub fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1b[0")?;
+        if self.bold {
+            f.write_str(";1")?;
+        }
+        if let Some(fg) = self.fg {
+            f.write_char(';')?;
+            fg.write_sgr(f, false)?;
+        }
+        if let Some(bg) = self.bg {
+            f.write_char(';')?;
+            bg.write_sgr(f, true)?;
+        }
+        f.write_char('m')
+    }
+}
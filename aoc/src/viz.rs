@@ -0,0 +1,381 @@
+//! A sink for debugging output such as map dumps and animations, which is
+//! discarded unless the runner was asked for it with `-v`, `--play` or
+//! `--viz FILE`.
+//!
+//! With `-v` dumps and each frame of a recording are printed to stderr as
+//! text. With `--play` recordings are played back instead, if stderr is a
+//! terminal. With `--viz FILE` they are written to FILE. If it ends in
+//! `.gif` or `.png`, each recording is instead saved next to it with its
+//! label added to the name, as an animated GIF (with the `gif` feature) or
+//! a sequence of numbered PNGs (with the `png` feature).
+
+use anyhow::{bail, Context, Result};
+
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::map::{Coords, DisplayTile, Map};
+use crate::style::{Color, Style};
+
+pub use crate::style::Rgb;
+
+enum Sink {
+    Off,
+    /// Stderr, where recordings are played back if `play` is set.
+    Stderr {
+        play: bool,
+    },
+    File(File),
+    /// An image path, which only recordings are written to.
+    Image(PathBuf),
+}
+
+static SINK: Mutex<Sink> = Mutex::new(Sink::Off);
+
+/// Sends output to stderr from now on.
+pub fn to_stderr() {
+    *SINK.lock().unwrap() = Sink::Stderr { play: false };
+}
+
+/// Sends output to stderr from now on, playing recordings back rather than
+/// printing their frames when it is a terminal.
+pub fn play_to_stderr() {
+    *SINK.lock().unwrap() = Sink::Stderr { play: true };
+}
+
+/// Sends output to `path` from now on, creating it if it is a text file.
+pub fn to_file(path: &Path) -> Result<()> {
+    let sink = match path.extension().and_then(|e| e.to_str()) {
+        Some("gif" | "png") => Sink::Image(path.to_owned()),
+        _ => Sink::File(
+            File::create(path).with_context(|| format!("failed to create {}", path.display()))?,
+        ),
+    };
+    *SINK.lock().unwrap() = sink;
+    Ok(())
+}
+
+/// Whether output goes anywhere, for skipping work that only feeds it.
+pub fn enabled() -> bool {
+    !matches!(*SINK.lock().unwrap(), Sink::Off)
+}
+
+/// Whether output goes to a terminal, and so can be coloured with
+/// [`Map::render`]`().styled(..)`.
+pub fn styled() -> bool {
+    matches!(*SINK.lock().unwrap(), Sink::Stderr { .. }) && std::io::stderr().is_terminal()
+}
+
+/// Writes `item`, typically a `Map`, to the sink under a heading.
+pub fn dump(label: &str, item: &impl Display) {
+    let mut sink = SINK.lock().unwrap();
+    let text = || format!("== {label} ==\n{item}\n\n");
+    // Debugging output is best effort, it should never fail a solve.
+    let _ = match &mut *sink {
+        Sink::Off | Sink::Image(_) => Ok(()),
+        Sink::Stderr { .. } => std::io::stderr().write_all(text().as_bytes()),
+        Sink::File(file) => file.write_all(text().as_bytes()),
+    };
+}
+
+/// Adds `label` to the file name of `path`, so that `out.gif` becomes
+/// `out-part-1.gif` for the label "part 1".
+fn labelled(path: &Path, label: &str) -> PathBuf {
+    let slug: String = (label.chars())
+        .map(|ch| if ch.is_alphanumeric() { ch } else { '-' })
+        .collect();
+    let mut name = path.with_extension("").into_os_string();
+    name.push(format!("-{slug}"));
+    let mut name = PathBuf::from(name);
+    if let Some(ext) = path.extension() {
+        name.set_extension(ext);
+    }
+    name
+}
+
+/// Records frames of a `Map` to be played back or saved once the solver is
+/// done with them.
+///
+/// Recording does nothing unless visualisation was enabled when the
+/// recorder was created, so solvers can record unconditionally.
+pub struct Recorder<T> {
+    frames: Vec<Map<T>>,
+    enabled: bool,
+    stride: usize,
+    calls: usize,
+    delay: Duration,
+    scale: usize,
+    colors: Option<Box<dyn Fn(T) -> Rgb>>,
+}
+
+impl<T: DisplayTile> Default for Recorder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: DisplayTile> Recorder<T> {
+    pub fn new() -> Self {
+        Recorder {
+            frames: Vec::new(),
+            enabled: enabled(),
+            stride: 1,
+            calls: 0,
+            delay: Duration::from_millis(50),
+            scale: 4,
+            colors: None,
+        }
+    }
+
+    /// Only keeps every `stride`th frame, for long simulations.
+    pub fn with_stride(mut self, stride: usize) -> Self {
+        self.stride = stride.max(1);
+        self
+    }
+
+    /// Sets the time each frame is shown for.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Sets the size in pixels of each tile in saved images.
+    pub fn with_scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Colours each tile with `colors` instead of its
+    /// [`DisplayTile::to_style`], in both the terminal and saved images.
+    pub fn with_colors(mut self, colors: impl Fn(T) -> Rgb + 'static) -> Self {
+        self.colors = Some(Box::new(colors));
+        self
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn frames(&self) -> &[Map<T>] {
+        &self.frames
+    }
+
+    /// Records the current state of `map`.
+    pub fn record(&mut self, map: &Map<T>) {
+        self.record_with(map, []);
+    }
+
+    /// Records `map` with some tiles replaced, such as the position of a
+    /// robot that isn't stored in the map itself.
+    pub fn record_with(&mut self, map: &Map<T>, overlay: impl IntoIterator<Item = (Coords, T)>) {
+        if !self.enabled {
+            return;
+        }
+        self.calls += 1;
+        if !(self.calls - 1).is_multiple_of(self.stride) {
+            return;
+        }
+        let mut frame = map.clone();
+        for (coords, tile) in overlay {
+            frame[coords] = tile;
+        }
+        self.frames.push(frame);
+    }
+
+    /// The colour of a tile in images: from `colors` if set, otherwise the
+    /// tile's foreground colour, falling back to [`Rgb::for_char`].
+    fn color(&self, tile: T) -> Rgb {
+        match (&self.colors, tile.to_style().fg) {
+            (Some(colors), _) => colors(tile),
+            (None, Some(fg)) => fg.rgb(),
+            (None, None) => Rgb::for_char(tile.to_char()),
+        }
+    }
+
+    fn style(&self, tile: T) -> Style {
+        match &self.colors {
+            Some(colors) => Style::PLAIN.fg(Color::Rgb(colors(tile))),
+            None => tile.to_style(),
+        }
+    }
+
+    /// Sends the recording to wherever the runner was asked to put it.
+    pub fn finish(self, label: &str) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let mut sink = SINK.lock().unwrap();
+        match &mut *sink {
+            Sink::Off => Ok(()),
+            Sink::Stderr { play } => {
+                let mut stderr = std::io::stderr();
+                if *play && stderr.is_terminal() {
+                    Ok(self.play(&mut stderr)?)
+                } else {
+                    Ok(self.write_frames(label, &mut stderr)?)
+                }
+            }
+            Sink::File(file) => Ok(self.write_frames(label, file)?),
+            Sink::Image(path) => {
+                let path = labelled(path, label);
+                match path.extension().and_then(|e| e.to_str()) {
+                    Some("gif") => self.save_gif(&path),
+                    _ => self.save_pngs(&path),
+                }
+            }
+        }
+    }
+
+    /// Like [`finish`](Self::finish), but prints any error rather than
+    /// returning it, for solvers that can't fail. A recording is only
+    /// debugging output, so losing one shouldn't lose the answers.
+    pub fn finish_or_warn(self, label: &str) {
+        if let Err(err) = self.finish(label) {
+            crate::runner::print_error(&err);
+        }
+    }
+
+    /// Writes each frame as text under a heading.
+    pub fn write_frames(&self, label: &str, out: &mut impl Write) -> std::io::Result<()> {
+        for (n, frame) in self.frames.iter().enumerate() {
+            write!(out, "== {label} frame {n} ==\n{frame}\n\n")?;
+        }
+        Ok(())
+    }
+
+    /// Plays the recording back in a terminal, redrawing each frame in place.
+    pub fn play(&self, out: &mut impl Write) -> std::io::Result<()> {
+        // Clear the screen and hide the cursor while playing.
+        write!(out, "\x1b[2J\x1b[?25l")?;
+        for frame in &self.frames {
+            let mut text = String::from("\x1b[H");
+            for y in 0..frame.height() {
+                let mut current = Style::PLAIN;
+                for x in 0..frame.width() {
+                    let tile = frame[(x, y)];
+                    let style = self.style(tile);
+                    if style != current {
+                        // Writing to a String cannot fail.
+                        let _ = style.write_ansi(&mut text);
+                        current = style;
+                    }
+                    text.push(tile.to_char());
+                }
+                text += "\x1b[0m\n";
+            }
+            out.write_all(text.as_bytes())?;
+            out.flush()?;
+            std::thread::sleep(self.delay);
+        }
+        write!(out, "\x1b[?25h")
+    }
+
+    /// Renders a frame to an image with `scale` pixels per tile, as rows of
+    /// colours.
+    #[cfg_attr(not(any(feature = "gif", feature = "png")), allow(dead_code))]
+    fn render(&self, frame: &Map<T>) -> (usize, usize, Vec<Rgb>) {
+        let (width, height) = (frame.width() * self.scale, frame.height() * self.scale);
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.push(self.color(frame[(x / self.scale, y / self.scale)]));
+            }
+        }
+        (width, height, pixels)
+    }
+
+    /// Saves the recording as an animated GIF.
+    pub fn save_gif(&self, path: &Path) -> Result<()> {
+        #[cfg(feature = "gif")]
+        {
+            let Some(first) = self.frames.first() else {
+                bail!("no frames were recorded");
+            };
+            let mut palette: Vec<Rgb> = Vec::new();
+            let mut frames = Vec::with_capacity(self.frames.len());
+            for frame in &self.frames {
+                let (_, _, pixels) = self.render(frame);
+                let mut indices = Vec::with_capacity(pixels.len());
+                for pixel in pixels {
+                    let idx = match palette.iter().position(|c| *c == pixel) {
+                        Some(idx) => idx,
+                        None if palette.len() < 256 => {
+                            palette.push(pixel);
+                            palette.len() - 1
+                        }
+                        None => bail!("GIFs cannot use more than 256 colours"),
+                    };
+                    indices.push(idx as u8);
+                }
+                frames.push(indices);
+            }
+
+            let (width, height) = (first.width() * self.scale, first.height() * self.scale);
+            let flat: Vec<u8> = palette
+                .iter()
+                .flat_map(|Rgb(r, g, b)| [*r, *g, *b])
+                .collect();
+            let file = File::create(path)
+                .with_context(|| format!("failed to create {}", path.display()))?;
+            let mut encoder = gif::Encoder::new(
+                std::io::BufWriter::new(file),
+                u16::try_from(width).context("frame too wide for a GIF")?,
+                u16::try_from(height).context("frame too tall for a GIF")?,
+                &flat,
+            )?;
+            encoder.set_repeat(gif::Repeat::Infinite)?;
+            for indices in frames {
+                encoder.write_frame(&gif::Frame {
+                    width: width as u16,
+                    height: height as u16,
+                    delay: (self.delay.as_millis() / 10) as u16,
+                    buffer: indices.into(),
+                    ..gif::Frame::default()
+                })?;
+            }
+            Ok(())
+        }
+        #[cfg(not(feature = "gif"))]
+        {
+            let _ = path;
+            bail!("GIF support is disabled, enable the `gif` feature of the aoc crate")
+        }
+    }
+
+    /// Saves each frame as a PNG next to `path`, numbered from zero, so
+    /// that `out.png` gives `out-0000.png`, `out-0001.png` and so on.
+    pub fn save_pngs(&self, path: &Path) -> Result<()> {
+        #[cfg(feature = "png")]
+        {
+            let stem = path.with_extension("");
+            for (n, frame) in self.frames.iter().enumerate() {
+                let (width, height, pixels) = self.render(frame);
+                let mut name = stem.clone().into_os_string();
+                name.push(format!("-{n:04}.png"));
+                let name = PathBuf::from(name);
+
+                let file = File::create(&name)
+                    .with_context(|| format!("failed to create {}", name.display()))?;
+                let mut encoder =
+                    png::Encoder::new(std::io::BufWriter::new(file), width as u32, height as u32);
+                encoder.set_color(png::ColorType::Rgb);
+                encoder.set_depth(png::BitDepth::Eight);
+                let data: Vec<u8> = pixels
+                    .iter()
+                    .flat_map(|Rgb(r, g, b)| [*r, *g, *b])
+                    .collect();
+                encoder.write_header()?.write_image_data(&data)?;
+            }
+            Ok(())
+        }
+        #[cfg(not(feature = "png"))]
+        {
+            let _ = path;
+            bail!("PNG support is disabled, enable the `png` feature of the aoc crate")
+        }
+    }
+}
@@ -0,0 +1,6 @@
+//! Checks that the derive macros reject bad attributes with useful errors.
+
+#[test]
+fn compile_errors() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
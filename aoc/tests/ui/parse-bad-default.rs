@@ -0,0 +1,10 @@
+use aoc::prelude::*;
+
+#[derive(AocParse)]
+#[parse("{a}")]
+struct Single {
+    #[parse(default)]
+    a: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use aoc::prelude::*;
+
+#[derive(AocParse)]
+#[parse("p={pos.0},{pos.1}")]
+struct Point {
+    pos: (i32, i32, i32),
+}
+
+fn main() {}
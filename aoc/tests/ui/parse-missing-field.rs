@@ -0,0 +1,11 @@
+use aoc::prelude::*;
+
+#[derive(AocParse)]
+#[parse("{a},{b}")]
+struct Pair {
+    a: u32,
+    b: u32,
+    c: u32,
+}
+
+fn main() {}
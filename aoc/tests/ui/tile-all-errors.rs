@@ -0,0 +1,13 @@
+use aoc::prelude::*;
+
+// Every problem is reported, not just the first.
+#[derive(Clone, Copy, ParseTile, DisplayTile)]
+enum Tile {
+    #[tile('#', "x", fg = "purple")]
+    Wall,
+    #[tile('#', sparkly)]
+    Floor,
+    Missing,
+}
+
+fn main() {}
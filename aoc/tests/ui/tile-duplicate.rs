@@ -0,0 +1,15 @@
+use aoc::prelude::*;
+
+#[derive(Clone, Copy, ParseTile)]
+enum Tile {
+    #[tile('#')]
+    Wall,
+    #[tile('.', '#')]
+    Floor,
+    #[tile('a'..='z')]
+    Letter(char),
+    #[tile('x')]
+    Cross,
+}
+
+fn main() {}
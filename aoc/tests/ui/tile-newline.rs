@@ -0,0 +1,11 @@
+use aoc::prelude::*;
+
+#[derive(Clone, Copy, ParseTile)]
+enum Tile {
+    #[tile('#')]
+    Wall,
+    #[tile('.', '\n')]
+    Floor,
+}
+
+fn main() {}
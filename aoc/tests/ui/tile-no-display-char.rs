@@ -0,0 +1,12 @@
+use aoc::prelude::*;
+
+#[derive(Clone, Copy, DisplayTile)]
+enum Tile {
+    #[tile('#')]
+    Wall,
+    Floor,
+    #[tile(other)]
+    Unknown,
+}
+
+fn main() {}
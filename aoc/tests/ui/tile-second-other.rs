@@ -0,0 +1,13 @@
+use aoc::prelude::*;
+
+#[derive(Clone, Copy, ParseTile)]
+enum Tile {
+    #[tile('#')]
+    Wall,
+    #[tile(other)]
+    Floor,
+    #[tile(other)]
+    Unknown,
+}
+
+fn main() {}
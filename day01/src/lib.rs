@@ -0,0 +1,36 @@
+use aoc::prelude::*;
+
+struct Day1;
+
+#[main]
+impl Solver for Day1 {
+    type Input = (Vec<u32>, Vec<u32>);
+
+    fn parse(inp: &'static str) -> Result<(Vec<u32>, Vec<u32>)> {
+        let pairs = aoc::parse::run(lines(separated_pair(nom_u32, space1, nom_u32)), inp)?;
+        Ok(pairs.into_iter().unzip())
+    }
+
+    fn part1((left, right): &(Vec<u32>, Vec<u32>)) -> impl fmt::Display {
+        let (mut left, mut right) = (left.clone(), right.clone());
+        left.sort();
+        right.sort();
+        left.iter()
+            .zip(&right)
+            .map(|(a, b)| a.abs_diff(*b))
+            .sum::<u32>()
+    }
+
+    fn part2((left, right): &(Vec<u32>, Vec<u32>)) -> impl fmt::Display {
+        let count: Counter<u32> = right.iter().copied().collect();
+        left.iter().map(|&i| i * count.get(&i) as u32).sum::<u32>()
+    }
+}
+
+example_test!(
+    Day1,
+    example,
+    file = "examples/day01-1.txt",
+    part1 = 11,
+    part2 = 31
+);
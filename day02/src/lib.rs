@@ -0,0 +1,66 @@
+use aoc::prelude::*;
+
+fn is_safe_mono(row: &[u32], inc: bool) -> bool {
+    for idx in 0..row.len() - 1 {
+        if inc && row[idx + 1] > row[idx] && row[idx + 1] <= row[idx] + 3 {
+            continue;
+        }
+        if !inc && row[idx + 1] < row[idx] && row[idx + 1] + 3 >= row[idx] {
+            continue;
+        }
+        return false;
+    }
+    true
+}
+
+fn is_safe(row: &[u32]) -> bool {
+    is_safe_mono(row, true) || is_safe_mono(row, false)
+}
+
+fn is_safe_mono_skip(row: &[u32], inc: bool, skip: usize) -> bool {
+    let skip_fn = |idx| if idx >= skip { idx + 1 } else { idx };
+    for idx in 0..row.len() - 2 {
+        let (i1, i2) = (skip_fn(idx), skip_fn(idx + 1));
+        if inc && row[i2] > row[i1] && row[i2] <= row[i1] + 3 {
+            continue;
+        }
+        if !inc && row[i2] < row[i1] && row[i2] + 3 >= row[i1] {
+            continue;
+        }
+        return false;
+    }
+    true
+}
+
+fn is_safe_skip(row: &[u32], skip: usize) -> bool {
+    is_safe_mono_skip(row, true, skip) || is_safe_mono_skip(row, false, skip)
+}
+
+struct Day2;
+
+#[main]
+impl Solver for Day2 {
+    type Input = Vec<Vec<u32>>;
+
+    fn parse(inp: &'static str) -> Result<Vec<Vec<u32>>> {
+        aoc::parse::run(lines(numbers()), inp)
+    }
+
+    fn part1(rows: &Vec<Vec<u32>>) -> impl fmt::Display {
+        rows.iter().filter(|row| is_safe(row)).count()
+    }
+
+    fn part2(rows: &Vec<Vec<u32>>) -> impl fmt::Display {
+        (rows.iter())
+            .filter(|row| (0..row.len()).any(|i| is_safe_skip(row, i)))
+            .count()
+    }
+}
+
+example_test!(
+    Day2,
+    example,
+    file = "examples/day02-1.txt",
+    part1 = 2,
+    part2 = 4
+);
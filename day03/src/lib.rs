@@ -0,0 +1,66 @@
+use aoc::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+enum Token {
+    Mul(u64, u64),
+    Do(bool),
+    Char,
+}
+
+impl Token {
+    fn parse(i: &str) -> IResult<&str, Self> {
+        alt((
+            map(
+                tuple((tag("mul("), nom_u64, tag(","), nom_u64, tag(")"))),
+                |(_, a, _, b, _)| Token::Mul(a, b),
+            ),
+            value(Token::Do(true), tag("do()")),
+            value(Token::Do(false), tag("don't()")),
+            value(Token::Char, anychar),
+        ))(i)
+    }
+}
+
+struct Day3;
+
+#[main]
+impl Solver for Day3 {
+    type Input = Vec<Token>;
+
+    fn parse(inp: &'static str) -> Result<Vec<Token>> {
+        let mut tokens = iterator(inp, Token::parse);
+        let parsed = (&mut tokens).collect();
+        tokens.finish()?;
+        Ok(parsed)
+    }
+
+    fn part1(tokens: &Vec<Token>) -> impl fmt::Display {
+        (tokens.iter())
+            .map(|token| match token {
+                Token::Mul(a, b) => a * b,
+                _ => 0,
+            })
+            .sum::<u64>()
+    }
+
+    fn part2(tokens: &Vec<Token>) -> impl fmt::Display {
+        let mut enabled = true;
+        let mut sum = 0;
+        for token in tokens {
+            match token {
+                Token::Mul(a, b) if enabled => sum += a * b,
+                Token::Do(en) => enabled = *en,
+                _ => {}
+            }
+        }
+        sum
+    }
+}
+
+example_test!(
+    Day3,
+    example,
+    input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))\n",
+    part1 = 161,
+    part2 = 48
+);
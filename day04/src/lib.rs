@@ -0,0 +1,81 @@
+use aoc::prelude::*;
+
+#[derive(Copy, Clone, Debug, ParseTile, PartialEq, Eq)]
+enum Tile {
+    #[tile('X')]
+    X,
+    #[tile('M')]
+    M,
+    #[tile('A')]
+    A,
+    #[tile('S')]
+    S,
+}
+
+struct Day4;
+
+#[main]
+impl Solver for Day4 {
+    type Input = Map<Tile>;
+
+    fn parse(inp: &'static str) -> Result<Map<Tile>> {
+        aoc::parse::run(Map::<Tile>::parse, inp)
+    }
+
+    fn part1(map: &Map<Tile>) -> impl fmt::Display {
+        let mut count = 0;
+        for (cx, tile) in map {
+            if tile != &Tile::X {
+                continue;
+            }
+
+            for (cm, dir) in map.neigh(cx, true, true) {
+                if map[cm] != Tile::M {
+                    continue;
+                }
+                let Some(ca) = map.add(cm, dir) else { continue };
+                let Some(cs) = map.add(ca, dir) else { continue };
+                if map[ca] == Tile::A && map[cs] == Tile::S {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    fn part2(map: &Map<Tile>) -> impl fmt::Display {
+        let mut count = 0;
+        for (coord, tile) in map {
+            if tile != &Tile::A {
+                continue;
+            }
+            let neigh: SmallVec<[_; 4]> = map
+                .neigh(coord, false, true)
+                .into_iter()
+                .map(|(c, _)| map[c])
+                .collect();
+            if neigh.len() != 4 {
+                continue;
+            }
+            let xmas = matches!(
+                (neigh[0], neigh[1], neigh[2], neigh[3]),
+                (Tile::M, Tile::M, Tile::S, Tile::S)
+                    | (Tile::M, Tile::S, Tile::S, Tile::M)
+                    | (Tile::S, Tile::S, Tile::M, Tile::M)
+                    | (Tile::S, Tile::M, Tile::M, Tile::S)
+            );
+            if xmas {
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+example_test!(
+    Day4,
+    example,
+    file = "examples/day04-1.txt",
+    part1 = 18,
+    part2 = 9
+);
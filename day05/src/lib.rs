@@ -0,0 +1,88 @@
+use aoc::prelude::*;
+
+fn parse_ordering(i: &str) -> PResult<'_, Vec<(u32, u32)>> {
+    many1(terminated(
+        separated_pair(nom_u32, nom_char('|'), nom_u32),
+        line_ending,
+    ))(i)
+}
+
+fn parse_updates(i: &str) -> PResult<'_, Vec<Vec<u32>>> {
+    lines(numbers())(i)
+}
+
+fn in_order(row: &[u32], ords: &[(u32, u32)]) -> bool {
+    let idx: HashMap<_, _> = row.iter().enumerate().map(|(i, k)| (k, i)).collect();
+    ords.iter().all(|(a, b)| {
+        if let (Some(ia), Some(ib)) = (idx.get(a), idx.get(b)) {
+            *ia < *ib
+        } else {
+            true
+        }
+    })
+}
+
+fn order(row: &mut [u32], ords: &[(u32, u32)]) {
+    let set: HashSet<_> = row.iter().collect();
+    let ords: Vec<(u32, u32)> = ords
+        .iter()
+        .filter(|(a, b)| set.contains(a) && set.contains(b))
+        .copied()
+        .collect();
+
+    let find = |row: &[u32], val| row.iter().enumerate().find(|(_, x)| **x == val).unwrap().0;
+
+    loop {
+        let mut did_swap = false;
+        for &(a, b) in &ords {
+            let (i1, i2) = (find(row, a), find(row, b));
+            if i1 > i2 {
+                row.swap(i1, i2);
+                did_swap = true;
+            }
+        }
+        if !did_swap {
+            break;
+        }
+    }
+}
+
+struct Day5;
+
+#[main]
+impl Solver for Day5 {
+    type Input = (Vec<(u32, u32)>, Vec<Vec<u32>>);
+
+    fn parse(inp: &'static str) -> Result<Self::Input> {
+        aoc::parse::run(
+            separated_pair(parse_ordering, line_ending, parse_updates),
+            inp,
+        )
+    }
+
+    fn part1((ord, rows): &Self::Input) -> impl fmt::Display {
+        (rows.iter())
+            .filter(|row| in_order(row, ord))
+            .map(|row| row[row.len() / 2])
+            .sum::<u32>()
+    }
+
+    fn part2((ord, rows): &Self::Input) -> impl fmt::Display {
+        (rows.iter())
+            .filter(|row| !in_order(row, ord))
+            .map(|row| {
+                let mut row = row.clone();
+                order(&mut row, ord);
+                row[row.len() / 2]
+            })
+            .sum::<u32>()
+    }
+}
+
+example_test!(
+    Day5,
+    example,
+    file = "examples/day05-1.txt",
+    part1 = 143,
+    part2 = 123
+);
@@ -0,0 +1,103 @@
+use aoc::prelude::*;
+use aoc::viz::Recorder;
+
+#[derive(Copy, Clone, Debug, DisplayTile, ParseTile, PartialEq, Eq)]
+enum Tile {
+    #[tile('.')]
+    Empty,
+    #[tile('#', fg = "#787878")]
+    Wall,
+    #[tile('^', fg = "#e62828")]
+    Start,
+    #[tile('X', fg = "#e6c83c")]
+    Hit,
+}
+
+/// Marks every tile the guard walks over, calling `record` with the map
+/// and the guard's position at each step.
+fn patrol(
+    mut pos: Coords,
+    map: &Map<Tile>,
+    mut record: impl FnMut(&Map<Tile>, Coords),
+) -> Map<Tile> {
+    let mut map = map.clone();
+    let mut dir = Dir::N;
+    loop {
+        map[pos] = Tile::Hit;
+        record(&map, pos);
+        let Some(to) = map.add(pos, dir) else { break };
+        if map[to] == Tile::Wall {
+            dir += Dir::E;
+        } else {
+            pos = to;
+        }
+    }
+    map
+}
+
+fn does_loop(mut pos: Coords, map: &Map<Tile>, set: &mut HashSet<(Coords, Dir)>) -> bool {
+    set.clear();
+    let mut dir = Dir::N;
+    while set.insert((pos, dir)) {
+        let Some(to) = map.add(pos, dir) else {
+            return false;
+        };
+        if map[to] == Tile::Wall {
+            dir += Dir::E;
+        } else {
+            pos = to;
+        }
+    }
+    true
+}
+
+struct Day6;
+
+#[main]
+impl Solver for Day6 {
+    type Input = (Coords, Map<Tile>);
+
+    fn parse(inp: &'static str) -> Result<(Coords, Map<Tile>)> {
+        let map = aoc::parse::run(Map::<Tile>::parse, inp)?;
+        let (start, _) = (map.iter())
+            .find(|(_, t)| **t == Tile::Start)
+            .context("the map has no guard")?;
+        Ok((start, map))
+    }
+
+    fn part1(&(start, ref map): &(Coords, Map<Tile>)) -> impl fmt::Display {
+        let mut recorder = Recorder::new().with_stride(map.width() * map.height() / 500);
+        let map = patrol(start, map, |map, pos| {
+            recorder.record_with(map, [(pos, Tile::Start)])
+        });
+        recorder.finish_or_warn("guard");
+        map.iter().filter(|(_, t)| **t == Tile::Hit).count()
+    }
+
+    fn part2(&(start, ref map): &(Coords, Map<Tile>)) -> impl fmt::Display {
+        let mut map = patrol(start, map, |_, _| {});
+        let mut set = HashSet::new();
+        let mut count = 0;
+        map[start] = Tile::Start;
+        for idx in 0..map.width() * map.height() {
+            let coord = map.coords(idx);
+            if map[coord] != Tile::Hit {
+                continue;
+            }
+            map[coord] = Tile::Wall;
+            if does_loop(start, &map, &mut set) {
+                count += 1;
+            }
+            map[coord] = Tile::Hit;
+        }
+        count
+    }
+}
+
+example_test!(
+    Day6,
+    example,
+    file = "examples/day06-1.txt",
+    part1 = 41,
+    part2 = 6
+);
@@ -0,0 +1,86 @@
+use aoc::prelude::*;
+
+fn parse_calib(i: &str) -> PResult<'_, (u64, Vec<u64>)> {
+    pair(
+        terminated(nom_u64, nom_char(':')),
+        many1(preceded(nom_char(' '), nom_u64)),
+    )(i)
+}
+
+fn concatenate(a: u64, b: u64) -> u64 {
+    let fact = match b {
+        ..10 => 10,
+        10..100 => 100,
+        100..1000 => 1000,
+        1000..10000 => 10000,
+        10000..100000 => 100000,
+        _ => panic!("concat table too small"),
+    };
+    a * fact + b
+}
+
+fn possible(target: u64, operands: &[u64], concat: bool) -> bool {
+    let mut queue = VecDeque::new();
+    queue.push_back((operands[0], &operands[1..]));
+    while let Some((cur, rem)) = queue.pop_front() {
+        let [next, rest @ ..] = rem else {
+            if cur == target {
+                return true;
+            } else {
+                continue;
+            }
+        };
+        if cur + *next <= target {
+            queue.push_back((cur + *next, rest));
+        }
+        if cur * *next <= target {
+            queue.push_back((cur * *next, rest));
+        }
+        if concat {
+            assert!(
+                cur != 0 && *next != 0,
+                "concatenation is undefined for zero operands"
+            );
+            let c = concatenate(cur, *next);
+            if c <= target {
+                queue.push_back((c, rest));
+            }
+        }
+    }
+    false
+}
+
+struct Day7;
+
+#[main]
+impl Solver for Day7 {
+    type Input = Vec<(u64, Vec<u64>)>;
+
+    fn parse(inp: &'static str) -> Result<Vec<(u64, Vec<u64>)>> {
+        aoc::parse::run(lines(parse_calib), inp)
+    }
+
+    fn part1(calibrations: &Vec<(u64, Vec<u64>)>) -> impl fmt::Display {
+        (calibrations.iter())
+            .filter(|(target, operands)| possible(*target, operands, false))
+            .map(|(target, _)| target)
+            .sum::<u64>()
+    }
+
+    fn part2(calibrations: &Vec<(u64, Vec<u64>)>) -> impl fmt::Display {
+        (calibrations.iter())
+            .filter(|(target, operands)| {
+                possible(*target, operands, false) || possible(*target, operands, true)
+            })
+            .map(|(target, _)| target)
+            .sum::<u64>()
+    }
+}
+
+example_test!(
+    Day7,
+    example,
+    file = "examples/day07-1.txt",
+    part1 = 3749,
+    part2 = 11387
+);
@@ -0,0 +1,120 @@
+use aoc::prelude::*;
+
+use std::iter::repeat_n;
+
+fn part1(lens: &[usize]) -> usize {
+    let mut disk: Vec<Option<u16>> = Vec::new();
+    let mut id = 0u16;
+    let mut file = true;
+    for &len in lens {
+        disk.extend(repeat_n(
+            if file {
+                id += 1;
+                Some(id - 1)
+            } else {
+                None
+            },
+            len,
+        ));
+        file = !file;
+    }
+
+    let iter1 = disk.iter().copied().enumerate();
+    let mut iter2 = iter1.clone().rev().flat_map(|(i, v)| v.map(|x| (i, x)));
+    let mut checksum = 0;
+    let mut last_idx = disk.len();
+    for (idx, val) in iter1 {
+        if idx >= last_idx {
+            break;
+        }
+        let id = match val {
+            Some(v) => v,
+            None => {
+                let (idx2, val2) = iter2.next().expect("no more back elements");
+                last_idx = idx2;
+                if idx2 < idx {
+                    break;
+                }
+                val2
+            }
+        };
+
+        checksum += idx * id as usize;
+    }
+
+    checksum
+}
+
+fn part2(lens: &[usize]) -> usize {
+    //                    pos    len
+    let mut files: Vec<(usize, usize)> = Vec::new();
+    let mut spaces: Vec<(usize, usize)> = Vec::new();
+
+    let mut pos = 0;
+    let mut file = true;
+    for &len in lens {
+        if file {
+            files.push((pos, len));
+        } else {
+            spaces.push((pos, len));
+        }
+        pos += len;
+        file = !file;
+    }
+
+    'file_loop: for (idx, file) in files.iter_mut().enumerate().rev() {
+        for (space_idx, space) in spaces.iter_mut().enumerate() {
+            if idx > space_idx && space.1 >= file.1 {
+                // We should also merge the spaces around files original
+                // position, but that's not necessary for the correct
+                // solution.
+                file.0 = space.0;
+                space.1 -= file.1;
+                space.0 += file.1;
+                continue 'file_loop;
+            }
+        }
+    }
+
+    let mut checksum = 0;
+    for (idx, file) in files.iter().copied().enumerate() {
+        for i in 0..file.1 {
+            checksum += (file.0 + i) * idx;
+        }
+    }
+    checksum
+}
+
+struct Day9;
+
+#[main]
+impl Solver for Day9 {
+    type Input = Vec<usize>;
+
+    fn parse(inp: &'static str) -> Result<Vec<usize>> {
+        (inp.trim_end().chars())
+            .map(|ch| {
+                let len = ch
+                    .to_digit(10)
+                    .with_context(|| format!("unexpected {ch:?}"))?;
+                Ok(len as usize)
+            })
+            .collect()
+    }
+
+    fn part1(lens: &Vec<usize>) -> impl fmt::Display {
+        part1(lens)
+    }
+
+    fn part2(lens: &Vec<usize>) -> impl fmt::Display {
+        part2(lens)
+    }
+}
+
+example_test!(
+    Day9,
+    example,
+    input = "2333133121414131402\n",
+    part1 = 1928,
+    part2 = 2858
+);
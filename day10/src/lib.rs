@@ -0,0 +1,59 @@
+use aoc::prelude::*;
+
+#[derive(Copy, Clone, Debug, ParseTile, PartialEq, Eq)]
+#[tile('0'..='9')]
+struct Tile(u8);
+
+fn reachable(map: &Map<Tile>, head: Coords) -> (usize, usize) {
+    let mut queue = VecDeque::new();
+    let mut part1 = HashSet::new();
+    let mut part2 = 0;
+    queue.push_back(head);
+    while let Some(coord) = queue.pop_front() {
+        for (n, _) in map.neigh(coord, true, false) {
+            if map[n].0 != map[coord].0 + 1 {
+                continue;
+            }
+            if map[n] == Tile(9) {
+                part1.insert(n);
+                part2 += 1;
+            } else {
+                queue.push_back(n);
+            }
+        }
+    }
+    (part1.len(), part2)
+}
+
+fn trailheads(map: &Map<Tile>) -> impl Iterator<Item = (usize, usize)> + '_ {
+    (map.iter())
+        .filter(|(_, tile)| **tile == Tile(0))
+        .map(|(coord, _)| reachable(map, coord))
+}
+
+struct Day10;
+
+#[main]
+impl Solver for Day10 {
+    type Input = Map<Tile>;
+
+    fn parse(inp: &'static str) -> Result<Map<Tile>> {
+        aoc::parse::run(Map::<Tile>::parse, inp)
+    }
+
+    fn part1(map: &Map<Tile>) -> impl fmt::Display {
+        trailheads(map).map(|(part1, _)| part1).sum::<usize>()
+    }
+
+    fn part2(map: &Map<Tile>) -> impl fmt::Display {
+        trailheads(map).map(|(_, part2)| part2).sum::<usize>()
+    }
+}
+
+example_test!(
+    Day10,
+    example,
+    file = "examples/day10-1.txt",
+    part1 = 36,
+    part2 = 81
+);
@@ -0,0 +1,64 @@
+use aoc::prelude::*;
+
+fn even_split(i: u64) -> Option<(u64, u64)> {
+    let format = format!("{i}");
+    if format.len() % 2 == 0 {
+        let len = format.len() / 2;
+        Some((
+            format[..len].parse().unwrap(),
+            format[len..].parse().unwrap(),
+        ))
+    } else {
+        None
+    }
+}
+
+/// How many rocks `rock` becomes after `count` blinks.
+fn blink(memo: &mut Memo<(u64, usize), usize>, rock: u64, count: usize) -> usize {
+    memo.recurse((rock, count), |blink, (rock, count)| {
+        if count == 0 {
+            1
+        } else if rock == 0 {
+            blink((1, count - 1))
+        } else if let Some((a, b)) = even_split(rock) {
+            blink((a, count - 1)) + blink((b, count - 1))
+        } else {
+            blink((rock * 2024, count - 1))
+        }
+    })
+}
+
+struct Day11;
+
+#[main]
+impl Solver for Day11 {
+    type Input = Vec<u64>;
+
+    fn parse(inp: &'static str) -> Result<Vec<u64>> {
+        aoc::parse::run(numbers(), inp)
+    }
+
+    fn part1(rocks: &Vec<u64>) -> impl fmt::Display {
+        let mut memo = Memo::new();
+        rocks
+            .iter()
+            .map(|&rock| blink(&mut memo, rock, 25))
+            .sum::<usize>()
+    }
+
+    fn part2(rocks: &Vec<u64>) -> impl fmt::Display {
+        let mut memo = Memo::new();
+        rocks
+            .iter()
+            .map(|&rock| blink(&mut memo, rock, 75))
+            .sum::<usize>()
+    }
+}
+
+example_test!(
+    Day11,
+    example,
+    input = "125 17\n",
+    part1 = 55312,
+    part2 = 65601038650482u64
+);
@@ -0,0 +1,88 @@
+use aoc::prelude::*;
+
+/// Each region of the map, as its area and the edges of its plots that
+/// face out of it.
+fn regions(map: &Map<char>) -> Vec<(usize, HashSet<(Coords, Dir)>)> {
+    let mut remaining: HashSet<Coords> = map.iter().map(|(c, _)| c).collect();
+    let mut regions = Vec::new();
+
+    while let Some(point) = remaining.iter().copied().next() {
+        remaining.remove(&point);
+        let ch = map[point];
+        let mut edge = HashSet::new();
+        let mut region = HashSet::new();
+        let mut scan = HashSet::new();
+        scan.insert(point);
+        region.insert(point);
+        while let Some(point) = scan.iter().copied().next() {
+            scan.remove(&point);
+            region.insert(point);
+            remaining.remove(&point);
+            for dir in [Dir::N, Dir::E, Dir::S, Dir::W] {
+                let Some(neigh) = map.add(point, dir) else {
+                    edge.insert((point, dir));
+                    continue;
+                };
+                if map[neigh] == ch && !region.contains(&neigh) {
+                    scan.insert(neigh);
+                    region.insert(neigh);
+                    remaining.remove(&neigh);
+                } else if map[neigh] != ch {
+                    edge.insert((point, dir));
+                }
+            }
+        }
+        regions.push((region.len(), edge));
+    }
+    regions
+}
+
+/// The number of straight sides that `edge` makes up.
+fn sides(map: &Map<char>, mut edge: HashSet<(Coords, Dir)>) -> usize {
+    let mut sides = 0;
+    while let Some((coord, dir)) = edge.iter().copied().next() {
+        edge.remove(&(coord, dir));
+        sides += 1;
+        for trans_dir in [Dir::E, Dir::W] {
+            let mut pos = coord;
+            while let Some(new_pos) = map.add(pos, dir + trans_dir) {
+                pos = new_pos;
+                if !edge.remove(&(pos, dir)) {
+                    break;
+                }
+            }
+        }
+    }
+    sides
+}
+
+struct Day12;
+
+#[main]
+impl Solver for Day12 {
+    type Input = Map<char>;
+
+    fn parse(inp: &'static str) -> Result<Map<char>> {
+        aoc::parse::run(Map::<char>::parse, inp)
+    }
+
+    fn part1(map: &Map<char>) -> impl fmt::Display {
+        (regions(map).into_iter())
+            .map(|(area, edge)| area * edge.len())
+            .sum::<usize>()
+    }
+
+    fn part2(map: &Map<char>) -> impl fmt::Display {
+        (regions(map).into_iter())
+            .map(|(area, edge)| area * sides(map, edge))
+            .sum::<usize>()
+    }
+}
+
+example_test!(
+    Day12,
+    example,
+    file = "examples/day12-1.txt",
+    part1 = 1930,
+    part2 = 1206
+);
@@ -0,0 +1,55 @@
+use aoc::linalg;
+use aoc::prelude::*;
+
+#[derive(Clone, Copy, Debug, AocParse)]
+#[parse("Button A: X+{a.0}, Y+{a.1}\nButton B: X+{b.0}, Y+{b.1}\nPrize: X={p.0}, Y={p.1}\n")]
+struct Crane {
+    a: (i64, i64),
+    b: (i64, i64),
+    p: (i64, i64),
+}
+
+/// The tokens it takes to press the A and B buttons.
+const COST: [i64; 2] = [3, 1];
+
+impl Crane {
+    /// The fewest tokens needed to win the prize, if it can be won.
+    fn cost(&self) -> Option<i64> {
+        let a = [[self.a.0, self.b.0], [self.a.1, self.b.1]];
+        let [pa, pb] = linalg::min_cost_solution(a, [self.p.0, self.p.1], COST)?;
+        Some(COST[0] * pa + COST[1] * pb)
+    }
+}
+
+const PART2_OFFSET: i64 = 10000000000000;
+
+struct Day13;
+
+#[main]
+impl Solver for Day13 {
+    type Input = Vec<Crane>;
+
+    fn parse(inp: &'static str) -> Result<Vec<Crane>> {
+        aoc::parse::run(blocks(Crane::parse), inp)
+    }
+
+    fn part1(cranes: &Vec<Crane>) -> impl fmt::Display {
+        cranes.iter().filter_map(Crane::cost).sum::<i64>()
+    }
+
+    fn part2(cranes: &Vec<Crane>) -> impl fmt::Display {
+        let far = |crane: &Crane| Crane {
+            p: (crane.p.0 + PART2_OFFSET, crane.p.1 + PART2_OFFSET),
+            ..*crane
+        };
+        cranes.iter().map(far).filter_map(|c| c.cost()).sum::<i64>()
+    }
+}
+
+example_test!(
+    Day13,
+    example,
+    file = "examples/day13-1.txt",
+    part1 = 480,
+    part2 = 875318608908i64
+);
@@ -0,0 +1,62 @@
+use aoc::prelude::*;
+
+mod part1;
+mod part2;
+
+fn expand_map2(map: &Map<part1::Tile>) -> Map<part2::Tile> {
+    let mut buf = Vec::with_capacity(map.tiles.len() * 2);
+    for tile in &map.tiles {
+        buf.extend(match *tile {
+            part1::Tile::Wall => [part2::Tile::Wall, part2::Tile::Wall],
+            part1::Tile::Box => [part2::Tile::BoxL, part2::Tile::BoxR],
+            part1::Tile::Space => [part2::Tile::Space, part2::Tile::Space],
+            part1::Tile::Start => [part2::Tile::Start, part2::Tile::Space],
+        })
+    }
+    Map::from_buf(map.width() * 2, buf)
+}
+
+struct Day15;
+
+#[main]
+impl Solver for Day15 {
+    type Input = (Map<part1::Tile>, Coords, Vec<Dir>);
+
+    fn parse(inp: &'static str) -> Result<Self::Input> {
+        let (map, moves) = aoc::parse::run(
+            separated_pair(
+                Map::<part1::Tile>::parse,
+                line_ending,
+                many1(preceded(
+                    opt(line_ending),
+                    alt((
+                        value(Dir::N, nom_char('^')),
+                        value(Dir::E, nom_char('>')),
+                        value(Dir::S, nom_char('v')),
+                        value(Dir::W, nom_char('<')),
+                    )),
+                )),
+            ),
+            inp,
+        )?;
+
+        let mut starts = (map.iter())
+            .filter(|(_, t)| **t == part1::Tile::Start)
+            .map(|(c, _)| c);
+        let start = starts.next().context("no start point")?;
+        if starts.next().is_some() {
+            return Err(anyhow!("multiple start positions"));
+        }
+        Ok((map, start, moves))
+    }
+
+    fn part1((map, start, moves): &Self::Input) -> impl fmt::Display {
+        part1::part1(map.clone(), *start, moves)
+    }
+
+    fn part2((map, start, moves): &Self::Input) -> impl fmt::Display {
+        part2::part2(expand_map2(map), (start.0 * 2, start.1), moves)
+    }
+}
+
+example_test!(Day15, example, file = "examples/day15-1.txt", part1 = 2028);
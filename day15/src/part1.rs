@@ -1,14 +1,15 @@
 use aoc::prelude::*;
+use aoc::viz::Recorder;
 
 #[derive(Copy, Clone, Debug, ParseTile, DisplayTile, PartialEq, Eq)]
 pub enum Tile {
-    #[tile('#')]
+    #[tile('#', fg = "#787878")]
     Wall,
-    #[tile('O')]
+    #[tile('O', fg = "#c88c3c")]
     Box,
     #[tile('.')]
     Space,
-    #[tile('@')]
+    #[tile('@', fg = "#e62828")]
     Start,
 }
 
@@ -26,19 +27,13 @@ fn xray_box_space(map: &Map<Tile>, mut coord: Coords, dir: Dir) -> Option<Coords
     }
 }
 
-pub fn part1(mut map: Map<Tile>, moves: &[Dir]) -> Result<usize> {
-    let Some(mut pos) = map
-        .iter()
-        .filter(|(_, t)| **t == Tile::Start)
-        .map(|(c, _)| c)
-        .next()
-    else {
-        Err(anyhow!("no start point"))?
-    };
-    println!("{map}\n");
+pub fn part1(mut map: Map<Tile>, mut pos: Coords, moves: &[Dir]) -> usize {
+    aoc::viz::dump("part 1 start", &map);
     map[pos] = Tile::Space;
 
+    let mut recorder = Recorder::new().with_stride(moves.len() / 500);
     for m in moves.iter().copied() {
+        recorder.record_with(&map, [(pos, Tile::Start)]);
         let Some(next) = map.add(pos, m) else {
             continue;
         };
@@ -52,12 +47,14 @@ pub fn part1(mut map: Map<Tile>, moves: &[Dir]) -> Result<usize> {
                     pos = next;
                 }
             }
-            Tile::Start => Err(anyhow!("multiple start positions"))?,
+            Tile::Start => unreachable!("parse checked for a single start"),
         }
     }
     map[pos] = Tile::Start;
 
-    println!("{map}");
+    recorder.record(&map);
+    recorder.finish_or_warn("part 1");
+    aoc::viz::dump("part 1 end", &map);
 
     let mut part1 = 0;
     for (coord, tile) in map.iter() {
@@ -66,5 +63,5 @@ pub fn part1(mut map: Map<Tile>, moves: &[Dir]) -> Result<usize> {
         }
     }
 
-    Ok(part1)
+    part1
 }
@@ -1,16 +1,17 @@
 use aoc::prelude::*;
+use aoc::viz::Recorder;
 
 #[derive(Copy, Clone, Debug, DisplayTile, PartialEq, Eq)]
 pub enum Tile {
-    #[tile('#')]
+    #[tile('#', fg = "#787878")]
     Wall,
-    #[tile('[')]
+    #[tile('[', fg = "#c88c3c")]
     BoxL,
-    #[tile(']')]
+    #[tile(']', fg = "#c88c3c")]
     BoxR,
     #[tile('.')]
     Space,
-    #[tile('@')]
+    #[tile('@', fg = "#e62828")]
     Start,
 }
 
@@ -92,19 +93,13 @@ fn push_to(map: &mut Map<Tile>, pos: Coords, dir: Dir) -> bool {
     true
 }
 
-pub fn part2(mut map: Map<Tile>, moves: &[Dir]) -> Result<usize> {
-    let Some(mut pos) = map
-        .iter()
-        .filter(|(_, t)| **t == Tile::Start)
-        .map(|(c, _)| c)
-        .next()
-    else {
-        Err(anyhow!("no start point"))?
-    };
-    println!("{map}\n");
+pub fn part2(mut map: Map<Tile>, mut pos: Coords, moves: &[Dir]) -> usize {
+    aoc::viz::dump("part 2 start", &map);
     map[pos] = Tile::Space;
 
+    let mut recorder = Recorder::new().with_stride(moves.len() / 500);
     for m in moves.iter().copied() {
+        recorder.record_with(&map, [(pos, Tile::Start)]);
         let Some(next) = map.add(pos, m) else {
             continue;
         };
@@ -114,7 +109,9 @@ pub fn part2(mut map: Map<Tile>, moves: &[Dir]) -> Result<usize> {
     }
     map[pos] = Tile::Start;
 
-    println!("{map}");
+    recorder.record(&map);
+    recorder.finish_or_warn("part 2");
+    aoc::viz::dump("part 2 end", &map);
 
     let mut part1 = 0;
     for (coord, tile) in map.iter() {
@@ -123,5 +120,5 @@ pub fn part2(mut map: Map<Tile>, moves: &[Dir]) -> Result<usize> {
         }
     }
 
-    Ok(part1)
+    part1
 }
@@ -0,0 +1,156 @@
+use aoc::prelude::*;
+use aoc::style::{Color, Style};
+
+#[derive(Copy, Clone, Debug, ParseTile, DisplayTile, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Tile {
+    #[tile('#', fg = "grey")]
+    Wall,
+    #[tile('.')]
+    Space,
+    #[tile('S')]
+    Start,
+    #[tile('E')]
+    End,
+}
+
+type Point = (Coords, Dir);
+
+fn coords_diff(point: Point, end: Coords) -> (usize, usize, Dir) {
+    if point.0 .0 >= end.0 && point.0 .1 >= end.1 {
+        (point.0 .0 - end.0, point.0 .1 - end.1, point.1)
+    } else if point.0 .0 >= end.0 && point.0 .1 < end.1 {
+        (point.0 .0 - end.0, end.1 - point.0 .1, point.1 + Dir::E)
+    } else if point.0 .0 < end.0 && point.0 .1 < end.1 {
+        (end.0 - point.0 .0, end.1 - point.0 .1, point.1 + Dir::S)
+    } else {
+        (end.0 - point.0 .0, point.0 .1 - end.1, point.1 + Dir::W)
+    }
+}
+
+/// Computes the cost of getting from point to end, assuming there are no
+/// walls in the path.
+///
+/// This is a lower bound for the true cost where walls are considered.
+fn heuristic(point: Point, end: Coords) -> usize {
+    let (x, y, d) = coords_diff(point, end);
+    if x == 0 && y == 0 {
+        0
+    } else if x == 0 {
+        match d {
+            Dir::N => y,
+            Dir::E | Dir::W => 1000 + y,
+            Dir::S => 2000 + y,
+            _ => unreachable!(),
+        }
+    } else if y == 0 {
+        match d {
+            Dir::W => y,
+            Dir::N | Dir::S => 1000 + y,
+            Dir::E => 2000 + y,
+            _ => unreachable!(),
+        }
+    } else {
+        match d {
+            Dir::N | Dir::W => 1000 + x + y,
+            Dir::S | Dir::E => 2000 + x + y,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// The maze with its start and end replaced by spaces.
+struct Maze {
+    map: Map<Tile>,
+    start: Coords,
+    end: Coords,
+}
+
+impl Maze {
+    /// Searches every cheapest path from the start to the end.
+    fn search(&self) -> aoc::astar::AStar<Point> {
+        let Maze { map, start, end } = self;
+        let astar = aoc::astar::AStar::run(
+            (*start, Dir::E),
+            |p| heuristic(*p, *end),
+            |p| {
+                let mut neighs = SmallVec::<[(Point, usize); 4]>::new();
+                let forwards = p.1.add_coords(p.0, 1);
+                if map[forwards] == Tile::Space {
+                    neighs.push(((forwards, p.1), 1));
+                }
+                neighs.push(((p.0, p.1 + Dir::E), 1000));
+                neighs.push(((p.0, p.1 + Dir::S), 2000));
+                neighs.push(((p.0, p.1 + Dir::W), 1000));
+                neighs.into_iter()
+            },
+            |p| p.0 == *end,
+        );
+        assert!(astar.triggered_end.is_some(), "no path from start to end");
+        astar
+    }
+}
+
+struct Day16;
+
+#[main]
+impl Solver for Day16 {
+    type Input = Maze;
+
+    fn parse(inp: &'static str) -> Result<Maze> {
+        let mut map = aoc::parse::run(Map::<Tile>::parse, inp)?;
+
+        let (start, _) = (map.iter())
+            .find(|(_, t)| **t == Tile::Start)
+            .context("expected start tile")?;
+        let (end, _) = (map.iter())
+            .find(|(_, t)| **t == Tile::End)
+            .context("expected end tile")?;
+        map[start] = Tile::Space;
+        map[end] = Tile::Space;
+
+        Ok(Maze { map, start, end })
+    }
+
+    fn part1(maze: &Maze) -> impl fmt::Display {
+        aoc::viz::dump("maze", &maze.map.render().styled(aoc::viz::styled()));
+
+        let astar = maze.search();
+        [Dir::N, Dir::E, Dir::S, Dir::W]
+            .into_iter()
+            .flat_map(|dir| astar.g_map.get(&(maze.end, dir)).copied())
+            .min()
+            .unwrap()
+    }
+
+    fn part2(maze: &Maze) -> impl fmt::Display {
+        let points: HashSet<_> = maze
+            .search()
+            .shortest_paths_nodes(
+                [Dir::N, Dir::E, Dir::S, Dir::W]
+                    .into_iter()
+                    .map(|dir| (maze.end, dir)),
+            )
+            .into_iter()
+            .map(|(p, _)| p)
+            .collect();
+
+        let path = Style::PLAIN.fg(Color::Red).bold();
+        aoc::viz::dump(
+            "best paths",
+            &(maze.map)
+                .render_with_overlay(&points, ('O', path))
+                .styled(aoc::viz::styled()),
+        );
+
+        points.len()
+    }
+}
+
+example_test!(
+    Day16,
+    example,
+    file = "examples/day16-1.txt",
+    part1 = 7036,
+    part2 = 45
+);
@@ -0,0 +1,152 @@
+use aoc::prelude::*;
+use std::fmt::Write;
+
+#[derive(Clone, Debug, AocParse)]
+#[parse("Register A: {a}\nRegister B: {b}\nRegister C: {c}\n\nProgram: {code:,}")]
+struct State {
+    a: u64,
+    b: u64,
+    c: u64,
+
+    #[parse(default)]
+    ip: usize,
+    code: Vec<u8>,
+}
+
+impl State {
+    fn single_step(&mut self) -> Option<Option<u8>> {
+        if self.ip >= self.code.len() {
+            None?;
+        }
+        let (opcode, operand) = (self.code[self.ip], self.code[self.ip + 1]);
+        let literal = || match operand {
+            0..7 => operand as u64,
+            _ => panic!("invalid operand {operand}"),
+        };
+        let combo = || match operand {
+            0..=3 => operand as u64,
+            4 => self.a,
+            5 => self.b,
+            6 => self.c,
+            _ => panic!("invalid operand {operand}"),
+        };
+
+        self.ip += 2;
+        match opcode {
+            0 => self.a >>= combo(),
+            1 => self.b ^= literal(),
+            2 => self.b = combo() & 0x07,
+            3 => {
+                if self.a != 0 {
+                    self.ip = literal() as usize;
+                }
+            }
+            4 => self.b ^= self.c,
+            5 => return Some(Some((combo() & 0x07) as u8)),
+            6 => self.b = self.a >> combo(),
+            7 => self.c = self.a >> combo(),
+            _ => panic!("invalid opcode {opcode}"),
+        }
+        Some(None)
+    }
+
+    fn reset(&mut self, a: u64) {
+        self.a = a;
+        self.b = 0;
+        self.c = 0;
+        self.ip = 0;
+    }
+
+    fn run(&mut self) -> Vec<u8> {
+        let mut out = Vec::new();
+        while let Some(s) = self.single_step() {
+            if let Some(val) = s {
+                out.push(val);
+            }
+        }
+        out
+    }
+
+    fn first_out(&mut self) -> Option<u8> {
+        while let Some(s) = self.single_step() {
+            if let Some(val) = s {
+                return Some(val);
+            }
+        }
+        None
+    }
+}
+
+fn list_to_str(out: &[u8]) -> String {
+    let mut s = String::new();
+    out.iter().fold(true, |f, c| {
+        if f {
+            write!(&mut s, "{c}")
+        } else {
+            write!(&mut s, ",{c}")
+        }
+        .unwrap();
+        false
+    });
+    s
+}
+
+struct Day17;
+
+#[main]
+impl Solver for Day17 {
+    type Input = State;
+
+    fn parse(inp: &'static str) -> Result<State> {
+        aoc::parse::run(State::parse, inp)
+    }
+
+    fn part1(state: &State) -> impl fmt::Display {
+        list_to_str(&state.clone().run())
+    }
+
+    fn part2(state: &State) -> impl fmt::Display {
+        let mut state = state.clone();
+        let mut reduced = [0u8; 1024];
+        for a in 0..1024 {
+            state.reset(a);
+            reduced[a as usize] = state.first_out().unwrap();
+        }
+
+        let mut strands: VecDeque<u64> = VecDeque::new();
+        let mut next = VecDeque::new();
+        strands.push_back(0);
+        for (idx, op) in state.code.iter().rev().copied().enumerate() {
+            assert!(!strands.is_empty(), "failed at step {idx}");
+            while let Some(strand) = strands.pop_front() {
+                for x in 0..=0b111 {
+                    let val = (strand << 3) | (x as u64);
+                    if reduced[(val as usize) & 1023] == op {
+                        next.push_back(val);
+                    }
+                }
+            }
+            std::mem::swap(&mut next, &mut strands);
+            next.clear();
+        }
+        let part2 = strands.pop_front().unwrap();
+        state.reset(part2);
+        vprintln!("Run:      {}", list_to_str(&state.run()));
+        vprintln!("Expected: {}", list_to_str(&state.code));
+        part2
+    }
+}
+
+example_test!(
+    Day17,
+    example1,
+    file = "examples/day17-1.txt",
+    part1 = "4,6,3,5,6,3,5,2,1,0"
+);
+example_test!(
+    Day17,
+    example2,
+    file = "examples/day17-2.txt",
+    part1 = "5,7,3,0",
+    part2 = 117440
+);
@@ -0,0 +1,75 @@
+use aoc::prelude::*;
+
+#[derive(Copy, Clone, Debug, ParseTile, DisplayTile, PartialEq, Eq)]
+#[repr(u8)]
+enum Tile {
+    #[tile('.')]
+    Safe,
+    #[tile('#')]
+    Corrupt,
+}
+
+fn gen_map(len: usize, input: &[Coords]) -> Map<Tile> {
+    let mut mem = Map::<Tile>::new(len, len, Tile::Safe);
+    for pos in input {
+        mem[*pos] = Tile::Corrupt;
+    }
+    mem
+}
+
+fn find_shortest(len: usize, input: &[Coords]) -> Option<usize> {
+    let mem = gen_map(len, input);
+
+    let start = (0, 0);
+    let h = |p: &Coords| (len - p.0) + (len - p.1);
+    let edges = |p: &Coords| {
+        mem.neigh(*p, true, false)
+            .into_iter()
+            .filter(|(c, _)| mem[*c] == Tile::Safe)
+            .map(move |(c, _)| (c, 1))
+    };
+    let end = |p: &Coords| *p == (len - 1, len - 1);
+
+    let astar = aoc::astar::AStar::run(start, h, edges, end);
+
+    astar.triggered_end.map(|e| astar.g_map[&e])
+}
+
+const LEN: usize = 71;
+
+struct Day18;
+
+#[main]
+impl Solver for Day18 {
+    type Input = Vec<Coords>;
+
+    fn parse(inp: &'static str) -> Result<Vec<Coords>> {
+        aoc::parse::run(
+            lines(map(
+                separated_pair(nom_u8, nom_char(','), nom_u8),
+                |(x, y)| (x as usize, y as usize),
+            )),
+            inp,
+        )
+    }
+
+    fn part1(coords: &Vec<Coords>) -> impl fmt::Display {
+        find_shortest(LEN, &coords[..1024]).unwrap()
+    }
+
+    fn part2(coords: &Vec<Coords>) -> impl fmt::Display {
+        assert!(find_shortest(LEN, coords).is_none());
+        let mut a = 1024;
+        let mut b = coords.len();
+        while a + 1 < b {
+            let x = (a + b) / 2;
+            if find_shortest(LEN, &coords[..x]).is_some() {
+                a = x;
+            } else {
+                b = x;
+            }
+        }
+        let (x, y) = coords[a];
+        format!("{x},{y}")
+    }
+}
@@ -0,0 +1,57 @@
+use aoc::prelude::*;
+
+/// The number of ways to make `input` from `patterns`. What is left of
+/// the input only matters by its length, so that is what is cached.
+fn count_tokens(input: &str, patterns: &[&str]) -> usize {
+    Memo::new().recurse_by_key(
+        input,
+        |rest| rest.len(),
+        |count, rest| {
+            if rest.is_empty() {
+                return 1;
+            }
+            (patterns.iter())
+                .filter_map(|pattern| rest.strip_prefix(pattern))
+                .map(count)
+                .sum()
+        },
+    )
+}
+
+struct Day19;
+
+#[main]
+impl Solver for Day19 {
+    type Input = (Vec<&'static str>, Vec<&'static str>);
+
+    fn parse(inp: &'static str) -> Result<Self::Input> {
+        aoc::parse::run(
+            separated_pair(
+                separated_list1(tag(", "), alpha1),
+                pair(line_ending, line_ending),
+                lines(alpha1),
+            ),
+            inp,
+        )
+    }
+
+    fn part1((patterns, inputs): &Self::Input) -> impl fmt::Display {
+        (inputs.iter())
+            .filter(|input| count_tokens(input, patterns) > 0)
+            .count()
+    }
+
+    fn part2((patterns, inputs): &Self::Input) -> impl fmt::Display {
+        (inputs.iter())
+            .map(|input| count_tokens(input, patterns))
+            .sum::<usize>()
+    }
+}
+
+example_test!(
+    Day19,
+    example,
+    file = "examples/day19-1.txt",
+    part1 = 6,
+    part2 = 16
+);
@@ -0,0 +1,357 @@
+use aoc::prelude::*;
+use nom::character::complete::digit0;
+
+fn from_keypad(ch: char) -> (i8, i8) {
+    match ch {
+        '0' => (1, 0),
+        'A' => (2, 0),
+        '1' => (0, 1),
+        '2' => (1, 1),
+        '3' => (2, 1),
+        '4' => (0, 2),
+        '5' => (1, 2),
+        '6' => (2, 2),
+        '7' => (0, 3),
+        '8' => (1, 3),
+        '9' => (2, 3),
+        _ => panic!("unknown keypad button: {ch:?}"),
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+struct Atom {
+    diff: (i8, i8),
+    hole: (i8, i8),
+    count: u8,
+}
+
+impl Atom {
+    fn from_num(i: &str) -> Vec<Atom> {
+        let mut into = Vec::new();
+        let mut pos = (2, 0);
+        for ch in i.chars().chain(['A']) {
+            let next = from_keypad(ch);
+            into.push(Atom {
+                diff: (next.0 - pos.0, next.1 - pos.1),
+                hole: (-pos.0, -pos.1),
+                count: 1,
+            });
+            pos = next;
+        }
+        into
+    }
+
+    fn layer(&self) -> SmallVec<[Vec<Atom>; 2]> {
+        assert!(self.count > 0);
+        let mut vec = SmallVec::new();
+        match self.diff {
+            (x @ 1.., 0) => vec.push(vec![
+                Atom {
+                    diff: (0, -1),
+                    hole: (-2, 0),
+                    count: x as u8,
+                },
+                Atom {
+                    diff: (0, 1),
+                    hole: (-2, 1),
+                    count: self.count,
+                },
+            ]),
+            (x @ ..0, 0) => vec.push(vec![
+                Atom {
+                    diff: (-2, -1),
+                    hole: (-2, 0),
+                    count: (-x) as u8,
+                },
+                Atom {
+                    diff: (2, 1),
+                    hole: (0, 1),
+                    count: self.count,
+                },
+            ]),
+            (0, y @ 1..) => vec.push(vec![
+                Atom {
+                    diff: (-1, 0),
+                    hole: (-2, 0),
+                    count: y as u8,
+                },
+                Atom {
+                    diff: (1, 0),
+                    hole: (-1, 0),
+                    count: self.count,
+                },
+            ]),
+            (0, y @ ..0) => vec.push(vec![
+                Atom {
+                    diff: (-1, -1),
+                    hole: (-2, 0),
+                    count: (-y) as u8,
+                },
+                Atom {
+                    diff: (1, 1),
+                    hole: (-1, 1),
+                    count: self.count,
+                },
+            ]),
+            (x @ 1.., y @ 1..) => {
+                if (self.hole.0 - x, self.hole.1) != (0, 0) {
+                    vec.push(vec![
+                        Atom {
+                            diff: (0, -1),
+                            hole: (-2, 0),
+                            count: x as u8,
+                        },
+                        Atom {
+                            diff: (-1, 1),
+                            hole: (-2, 1),
+                            count: y as u8,
+                        },
+                        Atom {
+                            diff: (1, 0),
+                            hole: (-1, 0),
+                            count: self.count,
+                        },
+                    ]);
+                }
+                if (self.hole.0, self.hole.1 - y) != (0, 0) {
+                    vec.push(vec![
+                        Atom {
+                            diff: (-1, 0),
+                            hole: (-2, 0),
+                            count: y as u8,
+                        },
+                        Atom {
+                            diff: (1, -1),
+                            hole: (-1, 0),
+                            count: x as u8,
+                        },
+                        Atom {
+                            diff: (0, 1),
+                            hole: (-2, 1),
+                            count: self.count,
+                        },
+                    ]);
+                }
+            }
+            (x @ ..0, y @ 1..) => {
+                if (self.hole.0 - x, self.hole.1) != (0, 0) {
+                    vec.push(vec![
+                        Atom {
+                            diff: (-2, -1),
+                            hole: (-2, 0),
+                            count: (-x) as u8,
+                        },
+                        Atom {
+                            diff: (1, 1),
+                            hole: (0, 1),
+                            count: y as u8,
+                        },
+                        Atom {
+                            diff: (1, 0),
+                            hole: (-1, 0),
+                            count: self.count,
+                        },
+                    ]);
+                }
+                if (self.hole.0, self.hole.1 - y) != (0, 0) {
+                    vec.push(vec![
+                        Atom {
+                            diff: (-1, 0),
+                            hole: (-2, 0),
+                            count: y as u8,
+                        },
+                        Atom {
+                            diff: (-1, -1),
+                            hole: (-1, 0),
+                            count: (-x) as u8,
+                        },
+                        Atom {
+                            diff: (2, 1),
+                            hole: (0, 1),
+                            count: self.count,
+                        },
+                    ]);
+                }
+            }
+            (x @ 1.., y @ ..0) => {
+                if (self.hole.0 - x, self.hole.1) != (0, 0) {
+                    vec.push(vec![
+                        Atom {
+                            diff: (0, -1),
+                            hole: (-2, 0),
+                            count: x as u8,
+                        },
+                        Atom {
+                            diff: (-1, 0),
+                            hole: (-2, 1),
+                            count: (-y) as u8,
+                        },
+                        Atom {
+                            diff: (1, 1),
+                            hole: (-1, 1),
+                            count: self.count,
+                        },
+                    ]);
+                }
+                if (self.hole.0, self.hole.1 - y) != (0, 0) {
+                    vec.push(vec![
+                        Atom {
+                            diff: (-1, -1),
+                            hole: (-2, 0),
+                            count: (-y) as u8,
+                        },
+                        Atom {
+                            diff: (1, 0),
+                            hole: (-1, 1),
+                            count: x as u8,
+                        },
+                        Atom {
+                            diff: (0, 1),
+                            hole: (-2, 1),
+                            count: self.count,
+                        },
+                    ]);
+                }
+            }
+            (x @ ..0, y @ ..0) => {
+                if (self.hole.0 - x, self.hole.1) != (0, 0) {
+                    vec.push(vec![
+                        Atom {
+                            diff: (-2, -1),
+                            hole: (-2, 0),
+                            count: (-x) as u8,
+                        },
+                        Atom {
+                            diff: (1, 0),
+                            hole: (0, 1),
+                            count: (-y) as u8,
+                        },
+                        Atom {
+                            diff: (1, 1),
+                            hole: (-1, 1),
+                            count: self.count,
+                        },
+                    ]);
+                }
+                if (self.hole.0, self.hole.1 - y) != (0, 0) {
+                    vec.push(vec![
+                        Atom {
+                            diff: (-1, -1),
+                            hole: (-2, 0),
+                            count: (-y) as u8,
+                        },
+                        Atom {
+                            diff: (-1, 0),
+                            hole: (-1, 1),
+                            count: (-x) as u8,
+                        },
+                        Atom {
+                            diff: (2, 1),
+                            hole: (0, 1),
+                            count: self.count,
+                        },
+                    ]);
+                }
+            }
+            (0, 0) => vec.push(vec![Atom {
+                diff: (0, 0),
+                hole: (-2, 0),
+                count: self.count,
+            }]),
+        }
+        vec
+    }
+
+    fn cost(&self) -> usize {
+        self.diff.0.unsigned_abs() as usize
+            + self.diff.1.unsigned_abs() as usize
+            + self.count as usize
+    }
+
+    fn cheapest(&self, n: usize) -> usize {
+        if n == 0 {
+            self.cost()
+        } else {
+            let possible = self.layer();
+            possible
+                .into_iter()
+                .map(|l| l.into_iter().map(|a| a.cheapest(n - 1)).sum())
+                .min()
+                .unwrap()
+        }
+    }
+
+    fn cheapest_seq(&self) -> Vec<Atom> {
+        let possible = self.layer();
+        let mut best = 0;
+        let mut dup = true;
+        let mut level = 0;
+        while dup {
+            level += 1;
+            let mut cost = usize::MAX;
+            for (idx, map) in possible.iter().enumerate() {
+                let c: usize = map.iter().map(|a| a.cheapest(level)).sum();
+                if c < cost {
+                    cost = c;
+                    best = idx;
+                    dup = false;
+                } else if c == cost {
+                    dup = true;
+                }
+            }
+        }
+        possible.into_iter().nth(best).unwrap()
+    }
+}
+
+fn cheapest(atoms: &[Atom], n: usize) -> usize {
+    let mut memo = Memo::new();
+    let mut counts: Counter<Atom> = atoms.iter().copied().collect();
+    for _ in 0..n {
+        counts = counts.flat_map_counts(|atom| {
+            let seq = memo.get_or_insert_with(*atom, || atom.cheapest_seq());
+            seq.iter()
+                .map(|&new| (new, 1))
+                .collect::<SmallVec<[_; 8]>>()
+        });
+    }
+    counts.iter().map(|(atom, count)| atom.cost() * count).sum()
+}
+
+/// The sum of each code's complexity with `n` robots using directional
+/// keypads.
+fn complexity(codes: &[&str], n: usize) -> usize {
+    (codes.iter())
+        .map(|code| {
+            let int: usize = code.parse().unwrap();
+            int * cheapest(&Atom::from_num(code), n)
+        })
+        .sum()
+}
+
+struct Day21;
+
+#[main]
+impl Solver for Day21 {
+    type Input = Vec<&'static str>;
+
+    fn parse(inp: &'static str) -> Result<Vec<&'static str>> {
+        aoc::parse::run(lines(terminated(digit0, nom_char('A'))), inp)
+    }
+
+    fn part1(codes: &Vec<&'static str>) -> impl fmt::Display {
+        complexity(codes, 2)
+    }
+
+    fn part2(codes: &Vec<&'static str>) -> impl fmt::Display {
+        complexity(codes, 25)
+    }
+}
+
+example_test!(
+    Day21,
+    example,
+    file = "examples/day21-1.txt",
+    part1 = 126384,
+    part2 = 154115708116294u64
+);
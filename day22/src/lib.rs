@@ -0,0 +1,90 @@
+use aoc::prelude::*;
+
+const MOD: u64 = 16777216;
+
+fn function(secret: u64) -> u64 {
+    let secret = ((secret * 64) ^ secret) % MOD;
+    let secret = ((secret / 32) ^ secret) % MOD;
+    ((secret * 2048) ^ secret) % MOD
+}
+
+#[derive(Clone, Debug)]
+struct Secrets(u64);
+
+impl Iterator for Secrets {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        self.0 = function(self.0);
+        Some(self.0)
+    }
+}
+
+struct Windows(i8, i8, i8, i8, u64);
+
+impl Windows {
+    fn new(start: u64) -> Self {
+        let a = function(start);
+        let b = function(a);
+        let c = function(b);
+        Self(
+            0,
+            (a % 10) as i8 - (start % 10) as i8,
+            (b % 10) as i8 - (a % 10) as i8,
+            (c % 10) as i8 - (b % 10) as i8,
+            c,
+        )
+    }
+}
+
+impl Iterator for Windows {
+    type Item = ([i8; 4], usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0 = self.1;
+        self.1 = self.2;
+        self.2 = self.3;
+        let x = function(self.4);
+        self.3 = (x % 10) as i8 - (self.4 % 10) as i8;
+        self.4 = x;
+        Some(([self.0, self.1, self.2, self.3], (self.4 % 10) as usize))
+    }
+}
+
+struct Day22;
+
+#[main]
+impl Solver for Day22 {
+    type Input = Vec<u64>;
+
+    fn parse(inp: &'static str) -> Result<Vec<u64>> {
+        Ok(aoc::fastparse::int_lines(inp)?)
+    }
+
+    fn part1(secrets: &Vec<u64>) -> impl fmt::Display {
+        (secrets.iter())
+            .map(|&secret| Secrets(secret).nth(1999).unwrap())
+            .sum::<u64>()
+    }
+
+    fn part2(secrets: &Vec<u64>) -> impl fmt::Display {
+        let mut part2_map = HashMap::<[i8; 4], usize>::new();
+        for &secret in secrets {
+            let mut seen = HashSet::new();
+            for (seq, price) in Windows::new(secret).take(2000 - 4) {
+                if seen.insert(seq) {
+                    *part2_map.entry(seq).or_default() += price;
+                }
+            }
+        }
+        part2_map.values().max().copied().unwrap()
+    }
+}
+
+example_test!(
+    Day22,
+    example1,
+    file = "examples/day22-1.txt",
+    part1 = 37327623
+);
+example_test!(Day22, example2, file = "examples/day22-2.txt", part2 = 23);